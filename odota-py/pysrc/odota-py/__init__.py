@@ -6,6 +6,7 @@ from . import odota_py
 class EntryType(Enum):
     ANY = "any"
     COSMETICS = "cosmetics"
+    COSMETIC = "cosmetic"
     DOTAPLUS = "dotaplus"
     EPILOGUE = "epilogue"
     CHAT_WHEEL = "chatwheel"
@@ -18,10 +19,32 @@ class EntryType(Enum):
     ACTIONS = "actions"
     DRAFT_START = "draft_start"
     DRAFT_TIMINGS = "draft_timings"
+    HERO_SWAP = "hero_swap"
+    GAME_STATE_CHANGE = "game_state_change"
     PLAYER_SLOT = "player_slot"
+    ROSTER = "roster"
+    KILL = "kill"
     INTERVAL = "interval"
+    POSITION = "position"
     STARTING_ITEMS = "StartingItems"
     ABILITY_LEVEL = "DotaAbilityLevel"
+    FACET = "facet"
+    SCEPTER = "scepter"
+    SHARD = "shard"
+    INVENTORY = "inventory"
+    HERO_STATE = "hero_state"
+    PAUSE = "pause"
+    UNPAUSE = "unpause"
+    CONNECTION = "connection"
+    CONNECTION_SUMMARY = "connection_summary"
+    ITEM_SOLD = "item_sold"
+    ITEM_REMOVED = "item_removed"
+    ITEM_DROPPED = "item_dropped"
+    ITEM_PICKED_UP = "item_picked_up"
+    ITEM_GIVEN = "item_given"
+    ITEM_CONSUMED = "item_consumed"
+    ITEM_COMBINED = "item_combined"
+    ITEM_DISASSEMBLED = "item_disassembled"
     # DotaCombatlogTypes
     INVALID = "DotaCombatlogInvalid"
     DAMAGE = "DotaCombatlogDamage"
@@ -85,8 +108,8 @@ class OdotaPy:
 
         return inner
 
-    def parse(self, binary: bytes):
-        output = odota_py.parse_replay(binary)
+    def parse(self, binary: bytes, **kwargs):
+        output = odota_py.parse_replay(binary, **kwargs)
         if len(self.callbacks) == 0:
             return
 
@@ -1,5 +1,5 @@
 # odota_py.pyi
-from typing import Optional, List
+from typing import Any, Dict, Optional, List
 
 class PyEntry:
     @property
@@ -203,4 +203,158 @@ class PyEntry:
     @property
     def stage(self) -> Optional[int]: ...
 
-def parse_replay(binary: bytes) -> List[PyEntry]: ...
\ No newline at end of file
+    @property
+    def game_mode(self) -> Optional[int]: ...
+
+    @property
+    def cooldown(self) -> Optional[float]: ...
+
+    @property
+    def health(self) -> Optional[int]: ...
+
+    @property
+    def max_health(self) -> Optional[int]: ...
+
+    @property
+    def mana(self) -> Optional[float]: ...
+
+    @property
+    def max_mana(self) -> Optional[float]: ...
+
+    @property
+    def strength(self) -> Optional[float]: ...
+
+    @property
+    def agility(self) -> Optional[float]: ...
+
+    @property
+    def intellect(self) -> Optional[float]: ...
+
+    @property
+    def armor(self) -> Optional[float]: ...
+
+    @property
+    def damage_min(self) -> Optional[int]: ...
+
+    @property
+    def damage_max(self) -> Optional[int]: ...
+
+    @property
+    def damage_bonus(self) -> Optional[int]: ...
+
+    @property
+    def move_speed(self) -> Optional[int]: ...
+
+    @property
+    def buyback_cooldown(self) -> Optional[float]: ...
+
+    @property
+    def world_x(self) -> Optional[float]: ...
+
+    @property
+    def world_y(self) -> Optional[float]: ...
+
+    @property
+    def world_z(self) -> Optional[float]: ...
+
+    @property
+    def target_slot(self) -> Optional[int]: ...
+
+    @property
+    def queue(self) -> Optional[bool]: ...
+
+    @property
+    def units(self) -> Optional[List[str]]: ...
+
+    @property
+    def channel(self) -> Optional[str]: ...
+
+    @property
+    def chat_event(self) -> Optional[Dict[str, Any]]: ...
+
+    @property
+    def tick(self) -> Optional[int]: ...
+
+    @property
+    def game_time(self) -> Optional[float]: ...
+
+    @property
+    def real_time(self) -> Optional[float]: ...
+
+    @property
+    def seq(self) -> Optional[int]: ...
+
+    @property
+    def duration(self) -> Optional[float]: ...
+
+    @property
+    def paused_time(self) -> Optional[float]: ...
+
+    @property
+    def role(self) -> Optional[str]: ...
+
+    @property
+    def player_id(self) -> Optional[int]: ...
+
+    @property
+    def account_id(self) -> Optional[int]: ...
+
+    @property
+    def steam_id(self) -> Optional[int]: ...
+
+    @property
+    def style(self) -> Optional[int]: ...
+
+    @property
+    def quality(self) -> Optional[int]: ...
+
+    @property
+    def stack_count(self) -> Optional[int]: ...
+
+    @property
+    def silence_modifier(self) -> Optional[bool]: ...
+
+    @property
+    def root_modifier(self) -> Optional[bool]: ...
+
+    @property
+    def reliable_gold(self) -> Optional[int]: ...
+
+    @property
+    def unreliable_gold(self) -> Optional[int]: ...
+
+    @property
+    def current_gold(self) -> Optional[int]: ...
+
+    @property
+    def gold_spent(self) -> Optional[int]: ...
+
+    @property
+    def buyback_cost(self) -> Optional[int]: ...
+
+    @property
+    def can_buyback(self) -> Optional[bool]: ...
+
+    @property
+    def assist_slots(self) -> Optional[List[int]]: ...
+
+    @property
+    def bounty(self) -> Optional[int]: ...
+
+    @property
+    def gold_lost(self) -> Optional[int]: ...
+
+    @property
+    def respawn_seconds(self) -> Optional[int]: ...
+
+def parse_replay(
+    binary: bytes,
+    interval: Optional[float] = 1.0,
+    max_pings: Optional[int] = 100000,
+    position_interval: Optional[float] = None,
+    unit_positions: bool = False,
+    inventory_interval: Optional[float] = None,
+    hero_state_interval: Optional[float] = None,
+    modifier_stack_events: bool = False,
+    chat_wheel_sounds: Optional[Dict[int, str]] = None,
+) -> List[PyEntry]: ...
\ No newline at end of file
@@ -343,6 +343,251 @@ impl PyEntry {
         self.inner.stage
     }
 
+    #[getter]
+    pub fn game_mode(&self) -> Option<i32> {
+        self.inner.game_mode
+    }
+
+    #[getter]
+    pub fn cooldown(&self) -> Option<f32> {
+        self.inner.cooldown
+    }
+
+    #[getter]
+    pub fn health(&self) -> Option<u32> {
+        self.inner.health
+    }
+
+    #[getter]
+    pub fn max_health(&self) -> Option<u32> {
+        self.inner.max_health
+    }
+
+    #[getter]
+    pub fn mana(&self) -> Option<f32> {
+        self.inner.mana
+    }
+
+    #[getter]
+    pub fn max_mana(&self) -> Option<f32> {
+        self.inner.max_mana
+    }
+
+    #[getter]
+    pub fn strength(&self) -> Option<f32> {
+        self.inner.strength
+    }
+
+    #[getter]
+    pub fn agility(&self) -> Option<f32> {
+        self.inner.agility
+    }
+
+    #[getter]
+    pub fn intellect(&self) -> Option<f32> {
+        self.inner.intellect
+    }
+
+    #[getter]
+    pub fn armor(&self) -> Option<f32> {
+        self.inner.armor
+    }
+
+    #[getter]
+    pub fn damage_min(&self) -> Option<u16> {
+        self.inner.damage_min
+    }
+
+    #[getter]
+    pub fn damage_max(&self) -> Option<u16> {
+        self.inner.damage_max
+    }
+
+    #[getter]
+    pub fn damage_bonus(&self) -> Option<u16> {
+        self.inner.damage_bonus
+    }
+
+    #[getter]
+    pub fn move_speed(&self) -> Option<u16> {
+        self.inner.move_speed
+    }
+
+    #[getter]
+    pub fn buyback_cooldown(&self) -> Option<f32> {
+        self.inner.buyback_cooldown
+    }
+
+    #[getter]
+    pub fn world_x(&self) -> Option<f32> {
+        self.inner.world_x
+    }
+
+    #[getter]
+    pub fn world_y(&self) -> Option<f32> {
+        self.inner.world_y
+    }
+
+    #[getter]
+    pub fn world_z(&self) -> Option<f32> {
+        self.inner.world_z
+    }
+
+    #[getter]
+    pub fn target_slot(&self) -> Option<i32> {
+        self.inner.target_slot
+    }
+
+    #[getter]
+    pub fn queue(&self) -> Option<bool> {
+        self.inner.queue
+    }
+
+    #[getter]
+    pub fn units(&self) -> Option<Vec<String>> {
+        self.inner.units.clone()
+    }
+
+    #[getter]
+    pub fn channel(&self) -> Option<String> {
+        self.inner.channel.clone()
+    }
+
+    #[getter]
+    pub fn chat_event(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let Some(event) = self.inner.chat_event.as_ref() else {
+            return Ok(None);
+        };
+        let json =
+            serde_json::to_string(event).map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Ok(Some(py.import_bound("json")?.call_method1("loads", (json,))?.unbind()))
+    }
+
+    #[getter]
+    pub fn tick(&self) -> Option<u32> {
+        self.inner.tick
+    }
+
+    #[getter]
+    pub fn game_time(&self) -> Option<f32> {
+        self.inner.game_time
+    }
+
+    #[getter]
+    pub fn real_time(&self) -> Option<f32> {
+        self.inner.real_time
+    }
+
+    #[getter]
+    pub fn seq(&self) -> Option<u64> {
+        self.inner.seq
+    }
+
+    #[getter]
+    pub fn duration(&self) -> Option<f32> {
+        self.inner.duration
+    }
+
+    #[getter]
+    pub fn paused_time(&self) -> Option<f32> {
+        self.inner.paused_time
+    }
+
+    #[getter]
+    pub fn role(&self) -> Option<String> {
+        self.inner.role.clone()
+    }
+
+    #[getter]
+    pub fn player_id(&self) -> Option<i32> {
+        self.inner.player_id
+    }
+
+    #[getter]
+    pub fn account_id(&self) -> Option<u32> {
+        self.inner.account_id
+    }
+
+    #[getter]
+    pub fn steam_id(&self) -> Option<u64> {
+        self.inner.steam_id
+    }
+
+    #[getter]
+    pub fn style(&self) -> Option<u8> {
+        self.inner.style
+    }
+
+    #[getter]
+    pub fn quality(&self) -> Option<u8> {
+        self.inner.quality
+    }
+
+    #[getter]
+    pub fn stack_count(&self) -> Option<u32> {
+        self.inner.stack_count
+    }
+
+    #[getter]
+    pub fn silence_modifier(&self) -> Option<bool> {
+        self.inner.silence_modifier
+    }
+
+    #[getter]
+    pub fn root_modifier(&self) -> Option<bool> {
+        self.inner.root_modifier
+    }
+
+    #[getter]
+    pub fn reliable_gold(&self) -> Option<u32> {
+        self.inner.reliable_gold
+    }
+
+    #[getter]
+    pub fn unreliable_gold(&self) -> Option<u32> {
+        self.inner.unreliable_gold
+    }
+
+    #[getter]
+    pub fn current_gold(&self) -> Option<u32> {
+        self.inner.current_gold
+    }
+
+    #[getter]
+    pub fn gold_spent(&self) -> Option<u32> {
+        self.inner.gold_spent
+    }
+
+    #[getter]
+    pub fn buyback_cost(&self) -> Option<u32> {
+        self.inner.buyback_cost
+    }
+
+    #[getter]
+    pub fn can_buyback(&self) -> Option<bool> {
+        self.inner.can_buyback
+    }
+
+    #[getter]
+    pub fn assist_slots(&self) -> Option<Vec<i32>> {
+        self.inner.assist_slots.clone()
+    }
+
+    #[getter]
+    pub fn bounty(&self) -> Option<u32> {
+        self.inner.bounty
+    }
+
+    #[getter]
+    pub fn gold_lost(&self) -> Option<u32> {
+        self.inner.gold_lost
+    }
+
+    #[getter]
+    pub fn respawn_seconds(&self) -> Option<i32> {
+        self.inner.respawn_seconds
+    }
+
     pub fn __repr__(&self) -> String {
         serde_json::to_string_pretty(&self.inner).unwrap()
     }
@@ -353,9 +598,41 @@ impl PyEntry {
 }
 
 #[pyfunction(name = "parse_replay")]
-pub fn parse_replay_py(binary: &[u8]) -> PyResult<Vec<PyEntry>> {
+#[pyo3(signature = (
+    binary,
+    interval = Some(1.0),
+    max_pings = Some(100000),
+    position_interval = None,
+    unit_positions = false,
+    inventory_interval = None,
+    hero_state_interval = None,
+    modifier_stack_events = false,
+    chat_wheel_sounds = None,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn parse_replay_py(
+    binary: &[u8],
+    interval: Option<f32>,
+    max_pings: Option<u32>,
+    position_interval: Option<f32>,
+    unit_positions: bool,
+    inventory_interval: Option<f32>,
+    hero_state_interval: Option<f32>,
+    modifier_stack_events: bool,
+    chat_wheel_sounds: Option<std::collections::HashMap<u32, String>>,
+) -> PyResult<Vec<PyEntry>> {
+    let config = Config {
+        interval,
+        max_pings,
+        position_interval,
+        unit_positions,
+        inventory_interval,
+        hero_state_interval,
+        modifier_stack_events,
+        chat_wheel_sounds: chat_wheel_sounds.unwrap_or_default().into_iter().collect(),
+    };
     std::panic::catch_unwind(|| {
-        Ok(parse_replay(binary)?
+        Ok(parse_replay_with_config(binary, &config)?
             .into_iter()
             .map(|x| PyEntry { inner: x })
             .collect::<Vec<_>>())
@@ -0,0 +1,281 @@
+//! Aggregations over `actions` entries.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use hashbrown::HashMap;
+use serde::Serialize;
+
+use crate::game_state::game_length;
+use crate::Entry;
+
+/// `dotaunitorder_t`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum OrderType {
+    None,
+    MoveToPosition,
+    MoveToTarget,
+    AttackMove,
+    AttackTarget,
+    CastPosition,
+    CastTarget,
+    CastTargetTree,
+    CastNoTarget,
+    CastToggle,
+    HoldPosition,
+    TrainAbility,
+    DropItem,
+    GiveItem,
+    PickupItem,
+    PickupRune,
+    PurchaseItem,
+    SellItem,
+    DisassembleItem,
+    MoveItem,
+    CastToggleAuto,
+    Stop,
+    Taunt,
+    Buyback,
+    Glyph,
+    EjectItemFromStash,
+    CastRune,
+    PingAbility,
+    MoveToDirection,
+    Patrol,
+    VectorTargetPosition,
+    Radar,
+    SetItemCombineLock,
+    Continue,
+    VectorTargetCanceled,
+    CastRiverPaint,
+    PregameAdjustItemAssignment,
+    DropItemAtFountain,
+    TakeItemFromNeutralItemStash,
+    MoveRelative,
+    CastToggleAlt,
+    Unknown(i32),
+}
+
+impl From<i32> for OrderType {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => OrderType::None,
+            1 => OrderType::MoveToPosition,
+            2 => OrderType::MoveToTarget,
+            3 => OrderType::AttackMove,
+            4 => OrderType::AttackTarget,
+            5 => OrderType::CastPosition,
+            6 => OrderType::CastTarget,
+            7 => OrderType::CastTargetTree,
+            8 => OrderType::CastNoTarget,
+            9 => OrderType::CastToggle,
+            10 => OrderType::HoldPosition,
+            11 => OrderType::TrainAbility,
+            12 => OrderType::DropItem,
+            13 => OrderType::GiveItem,
+            14 => OrderType::PickupItem,
+            15 => OrderType::PickupRune,
+            16 => OrderType::PurchaseItem,
+            17 => OrderType::SellItem,
+            18 => OrderType::DisassembleItem,
+            19 => OrderType::MoveItem,
+            20 => OrderType::CastToggleAuto,
+            21 => OrderType::Stop,
+            22 => OrderType::Taunt,
+            23 => OrderType::Buyback,
+            24 => OrderType::Glyph,
+            25 => OrderType::EjectItemFromStash,
+            26 => OrderType::CastRune,
+            27 => OrderType::PingAbility,
+            28 => OrderType::MoveToDirection,
+            29 => OrderType::Patrol,
+            30 => OrderType::VectorTargetPosition,
+            31 => OrderType::Radar,
+            32 => OrderType::SetItemCombineLock,
+            33 => OrderType::Continue,
+            34 => OrderType::VectorTargetCanceled,
+            35 => OrderType::CastRiverPaint,
+            36 => OrderType::PregameAdjustItemAssignment,
+            37 => OrderType::DropItemAtFountain,
+            38 => OrderType::TakeItemFromNeutralItemStash,
+            39 => OrderType::MoveRelative,
+            40 => OrderType::CastToggleAlt,
+            x => OrderType::Unknown(x),
+        }
+    }
+}
+
+impl Display for OrderType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            OrderType::None => "none",
+            OrderType::MoveToPosition => "move_to_position",
+            OrderType::MoveToTarget => "move_to_target",
+            OrderType::AttackMove => "attack_move",
+            OrderType::AttackTarget => "attack_target",
+            OrderType::CastPosition => "cast_position",
+            OrderType::CastTarget => "cast_target",
+            OrderType::CastTargetTree => "cast_target_tree",
+            OrderType::CastNoTarget => "cast_no_target",
+            OrderType::CastToggle => "cast_toggle",
+            OrderType::HoldPosition => "hold_position",
+            OrderType::TrainAbility => "train_ability",
+            OrderType::DropItem => "drop_item",
+            OrderType::GiveItem => "give_item",
+            OrderType::PickupItem => "pickup_item",
+            OrderType::PickupRune => "pickup_rune",
+            OrderType::PurchaseItem => "purchase_item",
+            OrderType::SellItem => "sell_item",
+            OrderType::DisassembleItem => "disassemble_item",
+            OrderType::MoveItem => "move_item",
+            OrderType::CastToggleAuto => "cast_toggle_auto",
+            OrderType::Stop => "stop",
+            OrderType::Taunt => "taunt",
+            OrderType::Buyback => "buyback",
+            OrderType::Glyph => "glyph",
+            OrderType::EjectItemFromStash => "eject_item_from_stash",
+            OrderType::CastRune => "cast_rune",
+            OrderType::PingAbility => "ping_ability",
+            OrderType::MoveToDirection => "move_to_direction",
+            OrderType::Patrol => "patrol",
+            OrderType::VectorTargetPosition => "vector_target_position",
+            OrderType::Radar => "radar",
+            OrderType::SetItemCombineLock => "set_item_combine_lock",
+            OrderType::Continue => "continue",
+            OrderType::VectorTargetCanceled => "vector_target_canceled",
+            OrderType::CastRiverPaint => "cast_river_paint",
+            OrderType::PregameAdjustItemAssignment => "pregame_adjust_item_assignment",
+            OrderType::DropItemAtFountain => "drop_item_at_fountain",
+            OrderType::TakeItemFromNeutralItemStash => "take_item_from_neutral_item_stash",
+            OrderType::MoveRelative => "move_relative",
+            OrderType::CastToggleAlt => "cast_toggle_alt",
+            OrderType::Unknown(x) => return write!(f, "{}", x),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl OrderType {
+    /// Order type of an `actions` entry
+    pub fn of(entry: &Entry) -> Option<OrderType> {
+        entry.key.as_ref()?.parse::<i32>().ok().map(OrderType::from)
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct ActionStats {
+    pub slot: i32,
+    pub total: u32,
+    /// Average over the game, pre-game actions excluded
+    pub apm: f32,
+    pub apm_by_minute: BTreeMap<i32, u32>,
+    /// Highest APM over any `window` seconds of the game
+    pub peak_apm: f32,
+    pub orders: HashMap<String, u32>,
+}
+
+fn actions(entries: &[Entry], slot: i32) -> impl Iterator<Item = &Entry> {
+    entries
+        .iter()
+        .filter(move |e| e.r#type.as_deref() == Some("actions") && e.slot == Some(slot))
+}
+
+/// Highest APM of a player over any `window` seconds inside `range`, e.g. a teamfight
+pub fn peak_apm(entries: &[Entry], slot: i32, range: Range<f32>, window: f32) -> f32 {
+    if window <= 0.0 {
+        return 0.0;
+    }
+
+    let mut times: Vec<f32> = actions(entries, slot)
+        .map(Entry::precise_time)
+        .filter(|t| range.contains(t))
+        .collect();
+    times.sort_by(f32::total_cmp);
+
+    let mut peak = 0;
+    let mut start = 0;
+    for end in 0..times.len() {
+        while times[end] - times[start] >= window {
+            start += 1;
+        }
+        peak = peak.max(end - start + 1);
+    }
+    peak as f32 * 60.0 / window
+}
+
+/// Per player APM and order histograms, `window` is the length in seconds of the peak APM window
+pub fn action_stats(entries: &[Entry], window: f32) -> Vec<ActionStats> {
+    // Replays parsed without `interval` entries or state changes fall back to the last entry
+    let duration = game_length(entries).unwrap_or_else(|| entries.iter().map(|e| e.time).fold(0.0, f32::max));
+
+    let mut slots: Vec<i32> = entries
+        .iter()
+        .filter(|e| e.r#type.as_deref() == Some("actions"))
+        .filter_map(|e| e.slot)
+        .collect();
+    slots.sort_unstable();
+    slots.dedup();
+
+    slots
+        .into_iter()
+        .map(|slot| {
+            let mut stats = ActionStats {
+                slot,
+                peak_apm: peak_apm(entries, slot, 0.0..duration, window),
+                ..Default::default()
+            };
+            for entry in actions(entries, slot) {
+                stats.total += 1;
+                *stats
+                    .apm_by_minute
+                    .entry((entry.time / 60.0).floor() as i32)
+                    .or_default() += 1;
+                if let Some(order) = OrderType::of(entry) {
+                    *stats.orders.entry(order.to_string()).or_default() += 1;
+                }
+            }
+            if duration > 0.0 {
+                let in_game = actions(entries, slot)
+                    .filter(|e| (0.0..=duration).contains(&e.time))
+                    .count();
+                stats.apm = in_game as f32 * 60.0 / duration;
+            }
+            stats
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(time: f32, r#type: &str) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = r#type.to_string().into();
+        entry
+    }
+
+    fn action(time: f32, order: i32) -> Entry {
+        let mut action = entry(time, "actions");
+        action.slot = Some(0);
+        action.key = order.to_string().into();
+        action
+    }
+
+    #[test]
+    fn apm_over_game_length() {
+        let mut post_game = entry(120.0, "game_state_change");
+        post_game.stage = Some(6);
+        let mut entries: Vec<Entry> = (0..240).map(|i| action(i as f32 / 2.0, 1)).collect();
+        entries.extend([action(-10.0, 4), post_game, action(130.0, 1), entry(300.0, "epilogue")]);
+
+        let stats = action_stats(&entries, 60.0);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].total, 242);
+        assert_eq!(stats[0].apm, 120.0);
+        assert_eq!(stats[0].peak_apm, 120.0);
+        assert_eq!(stats[0].orders["move_to_position"], 241);
+        assert_eq!(stats[0].orders["attack_target"], 1);
+    }
+}
@@ -0,0 +1,227 @@
+//! Radiant minus Dire networth and experience over time.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::Entry;
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct AdvantagePoint {
+    pub time: i32,
+    pub radiant_gold: i64,
+    pub dire_gold: i64,
+    pub radiant_xp: i64,
+    pub dire_xp: i64,
+    pub gold_adv: i64,
+    pub xp_adv: i64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LeadChange {
+    pub time: i32,
+    /// Team taking the lead, 2 for Radiant and 3 for Dire
+    pub team: i32,
+}
+
+/// Biggest change of an advantage inside the swing window
+#[derive(Serialize, Clone, Debug)]
+pub struct Swing {
+    pub start: i32,
+    pub end: i32,
+    /// Positive in Radiant's favor
+    pub change: i64,
+}
+
+#[derive(Serialize, Default)]
+pub struct Advantage {
+    pub per_second: Vec<AdvantagePoint>,
+    pub per_minute: Vec<AdvantagePoint>,
+    /// Radiant gold advantage at every minute, same as OpenDota's `radiant_gold_adv`
+    pub radiant_gold_adv: Vec<i64>,
+    pub radiant_xp_adv: Vec<i64>,
+    pub gold_lead_changes: Vec<LeadChange>,
+    pub xp_lead_changes: Vec<LeadChange>,
+    pub largest_gold_swing: Option<Swing>,
+    pub largest_xp_swing: Option<Swing>,
+}
+
+fn is_radiant(entry: &Entry) -> bool {
+    match entry.team {
+        Some(team) => team == 2,
+        None => entry.slot.is_some_and(|slot| slot < 5),
+    }
+}
+
+fn lead_changes(points: &[AdvantagePoint], adv: impl Fn(&AdvantagePoint) -> i64) -> Vec<LeadChange> {
+    let mut changes = vec![];
+    let mut leader = 0;
+    for point in points {
+        let current = adv(point).signum();
+        if current != 0 && current != leader {
+            if leader != 0 {
+                changes.push(LeadChange {
+                    time: point.time,
+                    team: if current > 0 { 2 } else { 3 },
+                });
+            }
+            leader = current;
+        }
+    }
+    changes
+}
+
+fn largest_swing(points: &[AdvantagePoint], window: i32, adv: impl Fn(&AdvantagePoint) -> i64) -> Option<Swing> {
+    let mut largest: Option<Swing> = None;
+    let mut first = 0;
+    for (end, point) in points.iter().enumerate() {
+        while point.time - points[first].time > window {
+            first += 1;
+        }
+        for start in &points[first..end] {
+            let change = adv(point) - adv(start);
+            if largest.as_ref().is_none_or(|s| change.abs() > s.change.abs()) {
+                largest = Some(Swing {
+                    start: start.time,
+                    end: point.time,
+                    change,
+                });
+            }
+        }
+    }
+    largest
+}
+
+/// Team totals from `interval` entries, `swing_window` is the length in seconds of the largest swing window
+pub fn advantage(entries: &[Entry], swing_window: i32) -> Advantage {
+    // Sampling faster than once per second yields several rows per player in the same second, keep the last one
+    let mut samples: BTreeMap<(i32, i32), &Entry> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.r#type.as_deref() == Some("interval")) {
+        if let Some(slot) = entry.slot {
+            samples.insert((entry.time as i32, slot), entry);
+        }
+    }
+
+    let mut totals: BTreeMap<i32, AdvantagePoint> = BTreeMap::new();
+    for ((time, _), entry) in samples {
+        let point = totals.entry(time).or_insert_with(|| AdvantagePoint {
+            time,
+            ..Default::default()
+        });
+        let gold = entry.networth.unwrap_or_default() as i64;
+        let xp = entry.xp.unwrap_or_default() as i64;
+        if is_radiant(entry) {
+            point.radiant_gold += gold;
+            point.radiant_xp += xp;
+        } else {
+            point.dire_gold += gold;
+            point.dire_xp += xp;
+        }
+    }
+
+    let per_second: Vec<AdvantagePoint> = totals
+        .into_values()
+        .map(|point| AdvantagePoint {
+            gold_adv: point.radiant_gold - point.dire_gold,
+            xp_adv: point.radiant_xp - point.dire_xp,
+            ..point
+        })
+        .collect();
+
+    let mut per_minute: Vec<AdvantagePoint> = vec![];
+    for point in per_second.iter().filter(|p| p.time >= 0) {
+        if per_minute.last().is_none_or(|last| point.time / 60 > last.time / 60) {
+            per_minute.push(point.clone());
+        }
+    }
+
+    Advantage {
+        radiant_gold_adv: per_minute.iter().map(|p| p.gold_adv).collect(),
+        radiant_xp_adv: per_minute.iter().map(|p| p.xp_adv).collect(),
+        gold_lead_changes: lead_changes(&per_second, |p| p.gold_adv),
+        xp_lead_changes: lead_changes(&per_second, |p| p.xp_adv),
+        largest_gold_swing: largest_swing(&per_second, swing_window, |p| p.gold_adv),
+        largest_xp_swing: largest_swing(&per_second, swing_window, |p| p.xp_adv),
+        per_second,
+        per_minute,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(advs: &[(i32, i64)]) -> Vec<AdvantagePoint> {
+        advs.iter()
+            .map(|&(time, gold_adv)| AdvantagePoint {
+                time,
+                gold_adv,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn changes(advs: &[(i32, i64)]) -> Vec<(i32, i32)> {
+        lead_changes(&points(advs), |p| p.gold_adv)
+            .into_iter()
+            .map(|c| (c.time, c.team))
+            .collect()
+    }
+
+    fn swing(advs: &[(i32, i64)], window: i32) -> Option<(i32, i32, i64)> {
+        largest_swing(&points(advs), window, |p| p.gold_adv).map(|s| (s.start, s.end, s.change))
+    }
+
+    fn interval(time: f32, slot: i32, networth: u32) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = "interval".to_string().into();
+        entry.slot = slot.into();
+        entry.networth = networth.into();
+        entry
+    }
+
+    #[test]
+    fn first_leader_is_not_a_change() {
+        assert_eq!(changes(&[(0, 0), (1, 0), (2, 300), (3, 500)]), vec![]);
+        assert_eq!(changes(&[(0, -100), (1, -200)]), vec![]);
+    }
+
+    #[test]
+    fn tie_keeps_the_leader() {
+        assert_eq!(changes(&[(0, 100), (1, 0), (2, 200)]), vec![]);
+        assert_eq!(changes(&[(0, 100), (1, 0), (2, -50)]), vec![(2, 3)]);
+    }
+
+    #[test]
+    fn sign_flips() {
+        assert_eq!(
+            changes(&[(0, 100), (1, -100), (2, -300), (3, 50)]),
+            vec![(1, 3), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn swing_window_boundary() {
+        let advs = [(0, 0), (10, 1000), (20, -500)];
+        assert_eq!(swing(&advs, 10), Some((10, 20, -1500)));
+        assert_eq!(swing(&advs, 20), Some((10, 20, -1500)));
+        assert_eq!(swing(&[(0, 0), (10, 1000)], 10), Some((0, 10, 1000)));
+        assert_eq!(swing(&[(0, 0), (11, 1000)], 10), None);
+    }
+
+    #[test]
+    fn several_samples_per_second() {
+        let entries = [
+            interval(5.0, 0, 1000),
+            interval(5.0, 5, 900),
+            interval(5.0, 0, 1100),
+            interval(5.0, 5, 950),
+            interval(6.0, 0, 1200),
+            interval(6.0, 5, 1000),
+        ];
+        let adv = advantage(&entries, 60);
+        let per_second: Vec<(i32, i64)> = adv.per_second.iter().map(|p| (p.time, p.gold_adv)).collect();
+        assert_eq!(per_second, vec![(5, 150), (6, 200)]);
+        assert_eq!(adv.radiant_gold_adv, vec![150]);
+    }
+}
@@ -0,0 +1,147 @@
+//! Ability and item casts correlated with orders and their effects.
+
+use std::ops::RangeInclusive;
+
+use hashbrown::HashMap;
+use serde::Serialize;
+
+use crate::Entry;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Cast {
+    pub slot: i32,
+    pub time: f32,
+    pub name: String,
+    pub item: bool,
+    pub level: Option<u8>,
+    pub target: Option<String>,
+    pub target_slot: Option<i32>,
+    /// Target position from the combat log or the order that issued the cast
+    pub position: Option<[f32; 2]>,
+    /// Damage or a modifier of the ability followed within the window
+    pub hit: bool,
+}
+
+/// Entries of `sorted` with a time inside `range`, `sorted` is ordered by time
+fn within<'a, 'b>(sorted: &'b [&'a Entry], range: RangeInclusive<f32>) -> &'b [&'a Entry] {
+    let start = sorted.partition_point(|e| e.precise_time() < *range.start());
+    let end = sorted.partition_point(|e| e.precise_time() <= *range.end());
+    &sorted[start..end.max(start)]
+}
+
+fn is_type(entry: &Entry, r#type: &str) -> bool {
+    entry.r#type.as_deref() == Some(r#type)
+}
+
+fn is_effect_of(effect: &Entry, ability: &str) -> bool {
+    let Some(inflictor) = effect.inflictor.as_deref() else {
+        return false;
+    };
+    if is_type(effect, "DotaCombatlogDamage") {
+        inflictor == ability
+    } else {
+        inflictor.contains(ability)
+    }
+}
+
+/// Casts of every player in time order, `window` is how many seconds around a cast are searched for
+/// the order that issued it and the damage or modifiers it caused
+pub fn cast_log(entries: &[Entry], window: f32) -> Vec<Cast> {
+    let mut effects: HashMap<&str, Vec<&Entry>> = HashMap::new();
+    let mut orders: HashMap<i32, Vec<&Entry>> = HashMap::new();
+    for entry in entries {
+        if is_type(entry, "DotaCombatlogDamage") || is_type(entry, "DotaCombatlogModifierAdd") {
+            if let Some(attacker) = entry.attackername.as_deref() {
+                effects.entry(attacker).or_default().push(entry);
+            }
+        } else if is_type(entry, "actions") && entry.world_x.is_some() {
+            if let Some(slot) = entry.slot {
+                orders.entry(slot).or_default().push(entry);
+            }
+        }
+    }
+    // Entries are ordered by whole seconds, order by the precise time for binary searches
+    for sorted in effects.values_mut().chain(orders.values_mut()) {
+        sorted.sort_by(|a, b| a.precise_time().total_cmp(&b.precise_time()));
+    }
+
+    entries
+        .iter()
+        .filter(|e| is_type(e, "DotaCombatlogAbility") || is_type(e, "DotaCombatlogItem"))
+        .filter_map(|e| {
+            let slot = e.slot?;
+            let name = e.inflictor.clone()?;
+            let cast_time = e.precise_time();
+
+            let position = e.world_x.zip(e.world_y).map(|(x, y)| [x, y]).or_else(|| {
+                within(orders.get(&slot)?, cast_time - window..=cast_time)
+                    .iter()
+                    .rev()
+                    .find(|o| o.inflictor.as_deref() == Some(name.as_str()))
+                    .and_then(|o| o.world_x.zip(o.world_y))
+                    .map(|(x, y)| [x, y])
+            });
+
+            let hit = e
+                .attackername
+                .as_deref()
+                .and_then(|attacker| effects.get(attacker))
+                .is_some_and(|effects| {
+                    within(effects, cast_time..=cast_time + window)
+                        .iter()
+                        .any(|x| is_effect_of(x, &name))
+                });
+
+            Some(Cast {
+                slot,
+                time: cast_time,
+                item: is_type(e, "DotaCombatlogItem"),
+                level: e.abilitylevel,
+                target: e.targetname.clone(),
+                target_slot: e.target_slot,
+                position,
+                hit,
+                name,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(time: f32, r#type: &str) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = r#type.to_string().into();
+        entry.slot = Some(0);
+        entry.attackername = "npc_dota_hero_lina".to_string().into();
+        entry.inflictor = "lina_light_strike_array".to_string().into();
+        entry
+    }
+
+    fn order(time: f32, x: f32) -> Entry {
+        let mut order = entry(time, "actions");
+        order.world_x = x.into();
+        order.world_y = 0.0.into();
+        order
+    }
+
+    #[test]
+    fn effects_and_orders_inside_the_window() {
+        let entries = [
+            order(7.0, 100.0),
+            order(9.5, 200.0),
+            entry(10.0, "DotaCombatlogAbility"),
+            entry(11.0, "DotaCombatlogDamage"),
+            entry(20.0, "DotaCombatlogAbility"),
+            entry(21.5, "DotaCombatlogDamage"),
+        ];
+        let casts = cast_log(&entries, 1.0);
+        assert_eq!(casts.len(), 2);
+        assert!(casts[0].hit);
+        assert_eq!(casts[0].position, Some([200.0, 0.0]));
+        assert!(!casts[1].hit);
+        assert_eq!(casts[1].position, None);
+    }
+}
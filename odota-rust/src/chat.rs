@@ -0,0 +1,537 @@
+//! Chat channels and chat wheel messages.
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use d2_stampede::proto::*;
+
+/// `DOTAChatChannelType_t`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum ChatChannel {
+    Regional,
+    Custom,
+    Party,
+    Lobby,
+    Team,
+    Guild,
+    Fantasy,
+    Whisper,
+    Console,
+    Tab,
+    Invalid,
+    All,
+    Allies,
+    Spectator,
+    Coaching,
+    Cafe,
+    CustomGame,
+    Private,
+    PostGame,
+    BattleCup,
+    HltvSpectator,
+    GameEvents,
+    Trivia,
+    Unknown(u32),
+}
+
+impl From<u32> for ChatChannel {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => ChatChannel::Regional,
+            1 => ChatChannel::Custom,
+            2 => ChatChannel::Party,
+            3 => ChatChannel::Lobby,
+            4 => ChatChannel::Team,
+            5 => ChatChannel::Guild,
+            6 => ChatChannel::Fantasy,
+            7 => ChatChannel::Whisper,
+            8 => ChatChannel::Console,
+            9 => ChatChannel::Tab,
+            10 => ChatChannel::Invalid,
+            11 => ChatChannel::All,
+            12 => ChatChannel::Allies,
+            13 => ChatChannel::Spectator,
+            14 => ChatChannel::Coaching,
+            15 => ChatChannel::Cafe,
+            16 => ChatChannel::CustomGame,
+            17 => ChatChannel::Private,
+            18 => ChatChannel::PostGame,
+            19 => ChatChannel::BattleCup,
+            20 => ChatChannel::HltvSpectator,
+            21 => ChatChannel::GameEvents,
+            22 => ChatChannel::Trivia,
+            x => ChatChannel::Unknown(x),
+        }
+    }
+}
+
+impl Display for ChatChannel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ChatChannel::Regional => "regional",
+            ChatChannel::Custom => "custom",
+            ChatChannel::Party => "party",
+            ChatChannel::Lobby => "lobby",
+            ChatChannel::Team => "team",
+            ChatChannel::Guild => "guild",
+            ChatChannel::Fantasy => "fantasy",
+            ChatChannel::Whisper => "whisper",
+            ChatChannel::Console => "console",
+            ChatChannel::Tab => "tab",
+            ChatChannel::Invalid => "invalid",
+            ChatChannel::All => "all",
+            ChatChannel::Allies => "allies",
+            ChatChannel::Spectator => "spectator",
+            ChatChannel::Coaching => "coaching",
+            ChatChannel::Cafe => "cafe",
+            ChatChannel::CustomGame => "custom_game",
+            ChatChannel::Private => "private",
+            ChatChannel::PostGame => "post_game",
+            ChatChannel::BattleCup => "battle_cup",
+            ChatChannel::HltvSpectator => "hltv_spectator",
+            ChatChannel::GameEvents => "game_events",
+            ChatChannel::Trivia => "trivia",
+            ChatChannel::Unknown(x) => return write!(f, "{}", x),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const CHAT_WHEEL: [&str; 72] = [
+    "Okay.",
+    "Careful!",
+    "Get Back!",
+    "We need wards.",
+    "Stun now!",
+    "Help!",
+    "Push now",
+    "Well played!",
+    "Missing!",
+    "Missing top!",
+    "Missing mid!",
+    "Missing bottom!",
+    "Go!",
+    "Initiate!",
+    "Follow me",
+    "Group up",
+    "Spread out",
+    "Split up farm",
+    "Attack now!",
+    "Be right back",
+    "Dive!",
+    "On my way",
+    "Get ready",
+    "Bait",
+    "Heal",
+    "Mana",
+    "Out of mana",
+    "Skill cooldown",
+    "Ultimate ready",
+    "Enemy returned",
+    "All enemy heroes missing!",
+    "Enemy incoming!",
+    "Invisible enemy nearby!",
+    "Enemy had rune",
+    "Split push",
+    "Coming to gank",
+    "Request gank",
+    "Fight under the tower",
+    "Deny the tower",
+    "Buy courier",
+    "Upgrade courier",
+    "Need detection",
+    "They have detection",
+    "Need a Town Portal Scroll",
+    "Reuse courier",
+    "Deward",
+    "Building Mekansm",
+    "Building Pipe",
+    "Stack neutrals",
+    "Pull creeps",
+    "Pull creeps",
+    "Enemy tower",
+    "Roshan",
+    "Affirmative",
+    "Wait",
+    "Pause",
+    "Current time",
+    "Check runes",
+    "Smoke gank",
+    "Good luck, have fun",
+    "Nice",
+    "Thanks!",
+    "Sorry",
+    "No give up!",
+    "That just happened",
+    "Game is hard",
+    "New meta",
+    "My bad",
+    "I immediately regret my decision",
+    "Relax, you're doing fine",
+    "Good game",
+    "Good game, well played",
+];
+
+/// Text of a standard chat wheel phrase, sounds and event phrases are not included, see
+/// `Config::chat_wheel_sounds`
+pub fn chat_wheel_message(id: u32) -> Option<&'static str> {
+    CHAT_WHEEL.get(id as usize).copied()
+}
+
+/// `CDotaUserMsgChatEvent` with player ids resolved to player indices `0..10`, like `slot` of other entries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ChatEvent {
+    HeroKill {
+        victim: Option<i32>,
+        killers: Vec<i32>,
+        gold: u32,
+    },
+    HeroDeny {
+        victim: Option<i32>,
+        denier: Option<i32>,
+    },
+    BarracksKill {
+        /// Bitmask of destroyed barracks
+        barracks: u32,
+    },
+    TowerKill {
+        killer: Option<i32>,
+        team: u32,
+    },
+    TowerDeny {
+        denier: Option<i32>,
+        team: u32,
+    },
+    FirstBlood {
+        killer: Option<i32>,
+        victim: Option<i32>,
+        gold: u32,
+    },
+    StreakKill {
+        killer: Option<i32>,
+        victim: Option<i32>,
+        gold: u32,
+    },
+    Buyback {
+        player: Option<i32>,
+        cost: u32,
+    },
+    Aegis {
+        player: Option<i32>,
+    },
+    AegisStolen {
+        player: Option<i32>,
+    },
+    AegisDenied {
+        player: Option<i32>,
+    },
+    RoshanKill {
+        team: i32,
+        gold: u32,
+    },
+    CourierLost {
+        team: i32,
+        killer: Option<i32>,
+    },
+    CourierRespawned {
+        team: i32,
+    },
+    GlyphUsed {
+        player: Option<i32>,
+    },
+    ItemPurchase {
+        player: Option<i32>,
+        item_id: u32,
+    },
+    RunePickup {
+        player: Option<i32>,
+        rune: u32,
+    },
+    RuneBottle {
+        player: Option<i32>,
+        rune: u32,
+    },
+    Connect {
+        player: Option<i32>,
+    },
+    Disconnect {
+        player: Option<i32>,
+    },
+    Reconnect {
+        player: Option<i32>,
+    },
+    PlayerLeft {
+        player: Option<i32>,
+    },
+    Paused {
+        player: Option<i32>,
+    },
+    Unpaused,
+    SuperCreeps {
+        team: i32,
+    },
+    Other {
+        players: Vec<i32>,
+        value: u32,
+    },
+}
+
+impl ChatEvent {
+    /// Decodes a chat event, `slot` maps player ids to player indices
+    pub fn decode(event: &CDotaUserMsgChatEvent, slot: impl Fn(i32) -> Option<i32>) -> ChatEvent {
+        let players = [
+            event.playerid_1(),
+            event.playerid_2(),
+            event.playerid_3(),
+            event.playerid_4(),
+            event.playerid_5(),
+            event.playerid_6(),
+        ];
+        ChatEvent::from_parts(event.r#type(), players, event.value(), slot)
+    }
+
+    /// Same as [`ChatEvent::decode`] over the fields of the message, `players` are `playerid_1` to
+    /// `playerid_6`
+    pub fn from_parts(
+        kind: DotaChatMessage,
+        players: [i32; 6],
+        value: u32,
+        slot: impl Fn(i32) -> Option<i32>,
+    ) -> ChatEvent {
+        let player1 = slot(players[0]);
+        let player2 = slot(players[1]);
+        match kind {
+            DotaChatMessage::ChatMessageHeroKill => ChatEvent::HeroKill {
+                victim: player1,
+                killers: players[1..].iter().copied().filter_map(&slot).collect(),
+                gold: value,
+            },
+            DotaChatMessage::ChatMessageHeroDeny => ChatEvent::HeroDeny {
+                victim: player1,
+                denier: player2,
+            },
+            DotaChatMessage::ChatMessageBarracksKill => ChatEvent::BarracksKill { barracks: value },
+            DotaChatMessage::ChatMessageTowerKill => ChatEvent::TowerKill {
+                killer: player1,
+                team: value,
+            },
+            DotaChatMessage::ChatMessageTowerDeny => ChatEvent::TowerDeny {
+                denier: player1,
+                team: value,
+            },
+            DotaChatMessage::ChatMessageFirstblood => ChatEvent::FirstBlood {
+                killer: player1,
+                victim: player2,
+                gold: value,
+            },
+            DotaChatMessage::ChatMessageStreakKill => ChatEvent::StreakKill {
+                killer: player1,
+                victim: player2,
+                gold: value,
+            },
+            DotaChatMessage::ChatMessageBuyback => ChatEvent::Buyback {
+                player: player1,
+                cost: value,
+            },
+            DotaChatMessage::ChatMessageAegis => ChatEvent::Aegis { player: player1 },
+            DotaChatMessage::ChatMessageAegisStolen => ChatEvent::AegisStolen { player: player1 },
+            DotaChatMessage::ChatMessageDeniedAegis => ChatEvent::AegisDenied { player: player1 },
+            DotaChatMessage::ChatMessageRoshanKill => ChatEvent::RoshanKill {
+                team: players[0],
+                gold: value,
+            },
+            DotaChatMessage::ChatMessageCourierLost => ChatEvent::CourierLost {
+                team: players[0],
+                killer: player2,
+            },
+            DotaChatMessage::ChatMessageCourierRespawned => ChatEvent::CourierRespawned { team: players[0] },
+            DotaChatMessage::ChatMessageGlyphUsed => ChatEvent::GlyphUsed { player: player1 },
+            DotaChatMessage::ChatMessageItemPurchase => ChatEvent::ItemPurchase {
+                player: player1,
+                item_id: value,
+            },
+            DotaChatMessage::ChatMessageRunePickup => ChatEvent::RunePickup {
+                player: player1,
+                rune: value,
+            },
+            DotaChatMessage::ChatMessageRuneBottle => ChatEvent::RuneBottle {
+                player: player1,
+                rune: value,
+            },
+            DotaChatMessage::ChatMessageConnect => ChatEvent::Connect { player: player1 },
+            DotaChatMessage::ChatMessageDisconnect => ChatEvent::Disconnect { player: player1 },
+            DotaChatMessage::ChatMessageReconnect => ChatEvent::Reconnect { player: player1 },
+            DotaChatMessage::ChatMessagePlayerLeft => ChatEvent::PlayerLeft { player: player1 },
+            DotaChatMessage::ChatMessagePaused => ChatEvent::Paused { player: player1 },
+            DotaChatMessage::ChatMessageUnpaused => ChatEvent::Unpaused,
+            DotaChatMessage::ChatMessageSuperCreeps => ChatEvent::SuperCreeps { team: players[0] },
+            _ => ChatEvent::Other {
+                players: players.into_iter().filter_map(&slot).collect(),
+                value,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use DotaChatMessage::*;
+
+    /// Player ids `0, 2, .., 18` to indices, unset ids are `-1`
+    fn slot(id: i32) -> Option<i32> {
+        (0..20).contains(&id).then_some(id / 2)
+    }
+
+    fn decode(kind: DotaChatMessage, players: [i32; 6], value: u32) -> ChatEvent {
+        ChatEvent::from_parts(kind, players, value, slot)
+    }
+
+    #[test]
+    fn kills() {
+        assert_eq!(
+            decode(ChatMessageHeroKill, [10, 2, 4, -1, -1, -1], 250),
+            ChatEvent::HeroKill {
+                victim: Some(5),
+                killers: vec![1, 2],
+                gold: 250
+            }
+        );
+        assert_eq!(
+            decode(ChatMessageFirstblood, [2, 10, -1, -1, -1, -1], 400),
+            ChatEvent::FirstBlood {
+                killer: Some(1),
+                victim: Some(5),
+                gold: 400
+            }
+        );
+        assert_eq!(
+            decode(ChatMessageStreakKill, [2, 10, -1, -1, -1, -1], 300),
+            ChatEvent::StreakKill {
+                killer: Some(1),
+                victim: Some(5),
+                gold: 300
+            }
+        );
+        assert_eq!(
+            decode(ChatMessageHeroDeny, [10, 12, -1, -1, -1, -1], 0),
+            ChatEvent::HeroDeny {
+                victim: Some(5),
+                denier: Some(6)
+            }
+        );
+    }
+
+    #[test]
+    fn buildings() {
+        assert_eq!(
+            decode(ChatMessageTowerKill, [4, -1, -1, -1, -1, -1], 3),
+            ChatEvent::TowerKill {
+                killer: Some(2),
+                team: 3
+            }
+        );
+        assert_eq!(
+            decode(ChatMessageTowerDeny, [4, -1, -1, -1, -1, -1], 2),
+            ChatEvent::TowerDeny {
+                denier: Some(2),
+                team: 2
+            }
+        );
+        assert_eq!(
+            decode(ChatMessageBarracksKill, [-1; 6], 0b11),
+            ChatEvent::BarracksKill { barracks: 0b11 }
+        );
+    }
+
+    #[test]
+    fn team_events_keep_team_numbers() {
+        assert_eq!(
+            decode(ChatMessageRoshanKill, [2, -1, -1, -1, -1, -1], 200),
+            ChatEvent::RoshanKill { team: 2, gold: 200 }
+        );
+        assert_eq!(
+            decode(ChatMessageCourierLost, [3, 4, -1, -1, -1, -1], 0),
+            ChatEvent::CourierLost {
+                team: 3,
+                killer: Some(2)
+            }
+        );
+        assert_eq!(
+            decode(ChatMessageCourierRespawned, [2, -1, -1, -1, -1, -1], 0),
+            ChatEvent::CourierRespawned { team: 2 }
+        );
+        assert_eq!(
+            decode(ChatMessageSuperCreeps, [3, -1, -1, -1, -1, -1], 0),
+            ChatEvent::SuperCreeps { team: 3 }
+        );
+    }
+
+    #[test]
+    fn player_events() {
+        let player = Some(9);
+        let players = [18, -1, -1, -1, -1, -1];
+        assert_eq!(
+            decode(ChatMessageBuyback, players, 1500),
+            ChatEvent::Buyback { player, cost: 1500 }
+        );
+        assert_eq!(decode(ChatMessageAegis, players, 0), ChatEvent::Aegis { player });
+        assert_eq!(
+            decode(ChatMessageAegisStolen, players, 0),
+            ChatEvent::AegisStolen { player }
+        );
+        assert_eq!(
+            decode(ChatMessageDeniedAegis, players, 0),
+            ChatEvent::AegisDenied { player }
+        );
+        assert_eq!(
+            decode(ChatMessageGlyphUsed, players, 0),
+            ChatEvent::GlyphUsed { player }
+        );
+        assert_eq!(
+            decode(ChatMessageItemPurchase, players, 116),
+            ChatEvent::ItemPurchase { player, item_id: 116 }
+        );
+        assert_eq!(
+            decode(ChatMessageRunePickup, players, 5),
+            ChatEvent::RunePickup { player, rune: 5 }
+        );
+        assert_eq!(
+            decode(ChatMessageRuneBottle, players, 5),
+            ChatEvent::RuneBottle { player, rune: 5 }
+        );
+        assert_eq!(decode(ChatMessageConnect, players, 0), ChatEvent::Connect { player });
+        assert_eq!(
+            decode(ChatMessageDisconnect, players, 0),
+            ChatEvent::Disconnect { player }
+        );
+        assert_eq!(
+            decode(ChatMessageReconnect, players, 0),
+            ChatEvent::Reconnect { player }
+        );
+        assert_eq!(
+            decode(ChatMessagePlayerLeft, players, 0),
+            ChatEvent::PlayerLeft { player }
+        );
+        assert_eq!(decode(ChatMessagePaused, players, 0), ChatEvent::Paused { player });
+        assert_eq!(decode(ChatMessageUnpaused, players, 0), ChatEvent::Unpaused);
+    }
+
+    #[test]
+    fn unset_players_and_other_events() {
+        assert_eq!(
+            decode(ChatMessagePaused, [-1; 6], 0),
+            ChatEvent::Paused { player: None }
+        );
+        assert_eq!(
+            decode(ChatMessageInvalid, [0, 2, -1, -1, -1, 4], 7),
+            ChatEvent::Other {
+                players: vec![0, 1, 2],
+                value: 7
+            }
+        );
+    }
+}
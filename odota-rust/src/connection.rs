@@ -0,0 +1,80 @@
+use anyhow::Result;
+
+use d2_stampede::prelude::*;
+
+use crate::{App, Entry};
+
+/// `DOTAConnectionState_t`
+const CONNECTED: u32 = 2;
+const DISCONNECTED: u32 = 3;
+const ABANDONED: u32 = 4;
+
+#[derive(Default)]
+pub(crate) struct ConnectionTracker {
+    states: [Option<u32>; 10],
+    disconnected_since: [Option<u32>; 10],
+    disconnected_ticks: [u32; 10],
+    disconnects: [u32; 10],
+    summary_processed: bool,
+}
+
+impl App {
+    pub(crate) fn process_connections(&mut self, ctx: &Context, pr: &Entity) -> Result<()> {
+        let tick = ctx.tick();
+        for i in 0..10 {
+            let idx = self.valid_indices[i];
+            let Some(state): Option<u32> = try_property!(pr, "m_vecPlayerData.{idx:04}.m_iConnectionState") else {
+                continue;
+            };
+            let previous = self.connections.states[i].replace(state);
+            if previous == Some(state) {
+                continue;
+            }
+
+            let key = match state {
+                CONNECTED if previous == Some(DISCONNECTED) => "reconnected",
+                CONNECTED => "connected",
+                DISCONNECTED => "disconnected",
+                ABANDONED => "abandoned",
+                _ => continue,
+            };
+
+            let mut entry = Entry::new(self.time(ctx)?);
+            entry.r#type = "connection".to_string().into();
+            entry.key = key.to_string().into();
+            entry.slot = (i as i32).into();
+            entry.player_slot = self.slot_to_players_slot.get(&(i as i32 + 1)).copied();
+            if state == DISCONNECTED {
+                self.connections.disconnected_since[i] = tick.into();
+                self.connections.disconnects[i] += 1;
+            } else if let Some(since) = self.connections.disconnected_since[i].take() {
+                let ticks = tick.saturating_sub(since);
+                self.connections.disconnected_ticks[i] += ticks;
+                entry.duration = (ticks as f32 / 30.0).into();
+            }
+            self.output(entry)?;
+        }
+        Ok(())
+    }
+
+    /// One `connection_summary` entry per player with total time disconnected in real seconds
+    pub(crate) fn process_connection_summary(&mut self, ctx: &Context) -> Result<()> {
+        if self.connections.summary_processed {
+            return Ok(());
+        }
+        self.connections.summary_processed = true;
+
+        let tick = ctx.tick();
+        for i in 0..10 {
+            let open = self.connections.disconnected_since[i].map_or(0, |since| tick.saturating_sub(since));
+            let mut entry = Entry::new(self.time(ctx)?);
+            entry.r#type = "connection_summary".to_string().into();
+            entry.slot = (i as i32).into();
+            entry.player_slot = self.slot_to_players_slot.get(&(i as i32 + 1)).copied();
+            entry.value = self.connections.disconnects[i].into();
+            entry.duration = ((self.connections.disconnected_ticks[i] + open) as f32 / 30.0).into();
+            self.output(entry)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Cosmetic items equipped by each player.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use hashbrown::{HashMap, HashSet};
+use serde::Serialize;
+
+use d2_stampede::prelude::*;
+
+use crate::{App, Entry, STEAM_ID_BASE};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Cosmetic {
+    pub player_slot: i32,
+    pub account_id: u32,
+    pub item_definition_index: u32,
+    pub style: Option<u8>,
+    pub quality: Option<u8>,
+    /// Class of the unit wearing the item, a hero, courier or ward
+    pub unit: Option<String>,
+}
+
+impl Cosmetic {
+    /// Cosmetic described by a `cosmetic` entry
+    pub fn of(entry: &Entry) -> Option<Cosmetic> {
+        if entry.r#type.as_deref() != Some("cosmetic") {
+            return None;
+        }
+        Some(Cosmetic {
+            player_slot: entry.player_slot?,
+            account_id: entry.account_id?,
+            item_definition_index: entry.value?,
+            style: entry.style,
+            quality: entry.quality,
+            unit: entry.unit.clone(),
+        })
+    }
+}
+
+/// Cosmetics grouped by player slot
+pub fn cosmetics_by_player(entries: &[Entry]) -> BTreeMap<i32, Vec<Cosmetic>> {
+    let mut players: BTreeMap<i32, Vec<Cosmetic>> = BTreeMap::new();
+    for cosmetic in entries.iter().filter_map(Cosmetic::of) {
+        players.entry(cosmetic.player_slot).or_default().push(cosmetic);
+    }
+    players
+}
+
+struct Wearable {
+    account_id: u32,
+    item_definition_index: u32,
+    style: Option<u8>,
+    quality: Option<u8>,
+    unit: Option<String>,
+}
+
+/// Wearables are created before player slots are known, owners are resolved at the end of the replay
+#[derive(Default)]
+pub(crate) struct CosmeticsTracker {
+    wearables: Vec<Wearable>,
+    seen: HashSet<(u32, u32, Option<String>)>,
+}
+
+impl App {
+    pub(crate) fn record_wearable(&mut self, ctx: &Context, wearable: &Entity) -> Result<()> {
+        let account_id: u64 = property!(wearable, "m_iAccountID");
+        let item_definition_index: i32 = property!(wearable, "m_iItemDefinitionIndex");
+        if account_id == 0 {
+            return Ok(());
+        }
+        let (account_id, item_definition_index) = (account_id as u32, item_definition_index as u32);
+
+        let unit = try_property!(wearable, "m_hOwnerEntity")
+            .and_then(|handle: usize| ctx.entities().get_by_handle(handle).ok())
+            .map(|owner| owner.class().name().to_string());
+        // Illusions and respawns recreate the same wearables
+        if !self
+            .cosmetics
+            .seen
+            .insert((account_id, item_definition_index, unit.clone()))
+        {
+            return Ok(());
+        }
+
+        self.cosmetics.wearables.push(Wearable {
+            account_id,
+            item_definition_index,
+            style: try_property!(wearable, "m_nOverrideStyle"),
+            quality: try_property!(wearable, "m_iEntityQuality"),
+            unit,
+        });
+        Ok(())
+    }
+
+    /// One `cosmetic` entry per worn item plus the `cosmetics` item to player slot map
+    pub(crate) fn process_cosmetics(&mut self, ctx: &Context) -> Result<()> {
+        let mut cosmetics_map: HashMap<u32, i32> = HashMap::new();
+        for wearable in std::mem::take(&mut self.cosmetics.wearables) {
+            let steam_id = STEAM_ID_BASE + wearable.account_id as u64;
+            let Some(&player_slot) = self.steam_id_to_player_slot.get(&steam_id) else {
+                continue;
+            };
+            cosmetics_map.insert(wearable.item_definition_index, player_slot);
+
+            let mut entry = Entry::new(self.time(ctx)?);
+            entry.r#type = "cosmetic".to_string().into();
+            entry.player_slot = player_slot.into();
+            entry.account_id = wearable.account_id.into();
+            entry.value = wearable.item_definition_index.into();
+            entry.style = wearable.style;
+            entry.quality = wearable.quality;
+            entry.unit = wearable.unit;
+            self.output(entry)?;
+        }
+
+        let mut entry = Entry::new(self.time(ctx)?);
+        entry.r#type = "cosmetics".to_string().into();
+        entry.key = serde_json::to_string(&cosmetics_map)?.into();
+        self.output(entry)
+    }
+}
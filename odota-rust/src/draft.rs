@@ -0,0 +1,389 @@
+use anyhow::Result;
+
+use d2_stampede::prelude::*;
+
+use crate::{App, Entry};
+
+const GAME_STATE_HERO_SELECTION: i32 = 2;
+const GAME_STATE_STRATEGY_TIME: i32 = 3;
+
+/// Captains Mode, Reverse Captains Mode and Captains Draft pick through `m_SelectedHeroes`,
+/// every other mode picks per player.
+fn is_captains_mode(game_mode: i32) -> bool {
+    matches!(game_mode, 2 | 8 | 16)
+}
+
+fn read_heroes(grp: &Entity, array: &str) -> Vec<i32> {
+    (0..)
+        .map_while(|i| try_property!(grp, "m_pGameRules.{array}.{i:04}"))
+        .collect()
+}
+
+/// Bans, team picks and heroes of every player at one tick
+#[derive(Default, Clone, Debug, PartialEq)]
+pub(crate) struct DraftState {
+    pub(crate) bans: Vec<i32>,
+    pub(crate) picks: Vec<i32>,
+    pub(crate) player_heroes: [i32; 10],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum DraftEvent {
+    Ban {
+        hero_id: i32,
+    },
+    /// Captains pick for their team
+    TeamPick {
+        hero_id: i32,
+    },
+    PlayerPick {
+        slot: usize,
+        hero_id: i32,
+        repick: bool,
+    },
+    /// Heroes traded during strategy time, not part of the draft order
+    Swap {
+        slot: usize,
+        hero_id: i32,
+        previous: i32,
+    },
+}
+
+/// Draft events between two consecutive states
+pub(crate) fn diff_draft(
+    previous: &DraftState,
+    current: &DraftState,
+    game_mode: i32,
+    game_state: i32,
+) -> Vec<DraftEvent> {
+    let mut events = vec![];
+
+    for (i, &hero_id) in current.bans.iter().enumerate() {
+        if hero_id > 0 && previous.bans.get(i) != Some(&hero_id) {
+            events.push(DraftEvent::Ban { hero_id });
+        }
+    }
+
+    if is_captains_mode(game_mode) {
+        for (i, &hero_id) in current.picks.iter().enumerate() {
+            if hero_id > 0 && previous.picks.get(i) != Some(&hero_id) {
+                events.push(DraftEvent::TeamPick { hero_id });
+            }
+        }
+        return events;
+    }
+
+    for (slot, (&hero_id, &before)) in current.player_heroes.iter().zip(&previous.player_heroes).enumerate() {
+        if hero_id <= 0 || hero_id == before {
+            continue;
+        }
+        if before <= 0 {
+            events.push(DraftEvent::PlayerPick {
+                slot,
+                hero_id,
+                repick: false,
+            });
+        } else if game_state == GAME_STATE_HERO_SELECTION {
+            events.push(DraftEvent::PlayerPick {
+                slot,
+                hero_id,
+                repick: true,
+            });
+        } else {
+            events.push(DraftEvent::Swap {
+                slot,
+                hero_id,
+                previous: before,
+            });
+        }
+    }
+    events
+}
+
+#[derive(Default)]
+pub(crate) struct Draft {
+    start_processed: bool,
+    order: u8,
+    state: DraftState,
+}
+
+impl App {
+    pub(crate) fn process_draft(&mut self, ctx: &Context, grp: &Entity) -> Result<()> {
+        let game_state: i32 = property!(grp, "m_pGameRules.m_nGameState");
+        if game_state != GAME_STATE_HERO_SELECTION && game_state != GAME_STATE_STRATEGY_TIME {
+            return Ok(());
+        }
+
+        if game_state == GAME_STATE_HERO_SELECTION && !self.draft.start_processed {
+            let x: u64 = property!(grp, "m_pGameRules.m_iPlayerIDsInControl");
+            if x != 0 {
+                let mut entry = Entry::new(self.time(ctx)?);
+                entry.r#type = "draft_start".to_string().into();
+                self.output(entry)?;
+                self.draft.start_processed = true;
+            }
+        }
+
+        let game_mode: i32 = try_property!(grp, "m_pGameRules.m_iGameMode").unwrap_or_default();
+        let active_team: Option<u8> = try_property!(grp, "m_pGameRules.m_iActiveTeam");
+
+        let pr = ctx.entities().get_by_class_name("CDOTA_PlayerResource").ok();
+        let mut current = DraftState {
+            bans: read_heroes(grp, "m_BannedHeroes"),
+            picks: read_heroes(grp, "m_SelectedHeroes"),
+            ..Default::default()
+        };
+        if let Some(pr) = pr {
+            for (i, hero) in current.player_heroes.iter_mut().enumerate() {
+                *hero = try_property!(pr, "m_vecPlayerTeamData.{i:04}.m_nSelectedHeroID").unwrap_or_default();
+            }
+        }
+
+        for event in diff_draft(&self.draft.state, &current, game_mode, game_state) {
+            let entry = match event {
+                DraftEvent::Ban { hero_id } => {
+                    let mut entry = self.draft_entry(ctx, grp, game_mode, hero_id, false)?;
+                    // Ranked ban votes are resolved all at once, only captains bans belong to a team
+                    if is_captains_mode(game_mode) {
+                        entry.team = active_team.map(i32::from);
+                        entry.player_slot = self.get_captain_slot(ctx, grp, active_team);
+                    }
+                    entry
+                }
+                DraftEvent::TeamPick { hero_id } => {
+                    let mut entry = self.draft_entry(ctx, grp, game_mode, hero_id, true)?;
+                    entry.team = active_team.map(i32::from);
+                    entry.player_slot = self.get_captain_slot(ctx, grp, active_team);
+                    entry
+                }
+                DraftEvent::PlayerPick { slot, hero_id, repick } => {
+                    let mut entry = self.draft_entry(ctx, grp, game_mode, hero_id, true)?;
+                    if let Some(pr) = pr {
+                        self.set_draft_player(pr, slot, &mut entry);
+                    }
+                    entry.repicked = repick.into();
+                    entry
+                }
+                DraftEvent::Swap {
+                    slot,
+                    hero_id,
+                    previous,
+                } => {
+                    let mut entry = Entry::new(self.time(ctx)?);
+                    entry.r#type = "hero_swap".to_string().into();
+                    entry.hero_id = hero_id.into();
+                    entry.value = (previous as u32).into();
+                    if let Some(pr) = pr {
+                        self.set_draft_player(pr, slot, &mut entry);
+                    }
+                    entry
+                }
+            };
+            self.output(entry)?;
+        }
+        self.draft.state = current;
+
+        Ok(())
+    }
+
+    fn get_captain_slot(&self, ctx: &Context, grp: &Entity, team: Option<u8>) -> Option<i32> {
+        let index = team?.checked_sub(2)?;
+        let player_id: i32 = try_property!(grp, "m_pGameRules.m_iCaptainPlayerIDs.{index:04}")?;
+        self.get_player_slot_by_id(ctx, player_id).ok()
+    }
+
+    fn set_draft_player(&self, pr: &Entity, slot: usize, entry: &mut Entry) {
+        let player_team: Option<i32> = try_property!(pr, "m_vecPlayerData.{slot:04}.m_iPlayerTeam");
+        let team_slot: Option<i32> = try_property!(pr, "m_vecPlayerTeamData.{slot:04}.m_iTeamSlot");
+        entry.team = player_team;
+        entry.slot = (slot as i32).into();
+        entry.player_slot = player_team
+            .zip(team_slot)
+            .map(|(team, team_slot)| if team == 2 { 0 } else { 128 } + team_slot);
+        entry.randomed = try_property!(pr, "m_vecPlayerTeamData.{slot:04}.m_bHasRandomed");
+    }
+
+    fn draft_entry(&mut self, ctx: &Context, grp: &Entity, game_mode: i32, hero_id: i32, pick: bool) -> Result<Entry> {
+        let extime0: Option<f32> = try_property!(grp, "m_pGameRules.m_fExtraTimeRemaining.0000");
+        let extime1: Option<f32> = try_property!(grp, "m_pGameRules.m_fExtraTimeRemaining.0001");
+
+        let mut entry = Entry::new(self.time(ctx)?);
+        entry.r#type = "draft_timings".to_string().into();
+        entry.draft_order = self.draft.order.into();
+        entry.pick = pick.into();
+        entry.hero_id = hero_id.into();
+        entry.game_mode = game_mode.into();
+        entry.draft_extime0 = extime0.map(|t| t.round() as u16);
+        entry.draft_extime1 = extime1.map(|t| t.round() as u16);
+        entry.draft_active_team = try_property!(grp, "m_pGameRules.m_iActiveTeam");
+
+        self.draft.order += 1;
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_PICK: i32 = 1;
+    const CAPTAINS_MODE: i32 = 2;
+    const REVERSE_CAPTAINS_MODE: i32 = 8;
+    const CAPTAINS_DRAFT: i32 = 16;
+    const TURBO: i32 = 23;
+
+    fn players(heroes: &[(usize, i32)]) -> [i32; 10] {
+        let mut player_heroes = [0; 10];
+        for &(slot, hero_id) in heroes {
+            player_heroes[slot] = hero_id;
+        }
+        player_heroes
+    }
+
+    fn captains_mode_sequence(game_mode: i32) {
+        let start = DraftState {
+            bans: vec![0; 14],
+            picks: vec![0; 10],
+            ..Default::default()
+        };
+        let mut banned = start.clone();
+        banned.bans[0] = 1;
+        assert_eq!(
+            diff_draft(&start, &banned, game_mode, GAME_STATE_HERO_SELECTION),
+            vec![DraftEvent::Ban { hero_id: 1 }]
+        );
+
+        let mut picked = banned.clone();
+        picked.picks[0] = 2;
+        assert_eq!(
+            diff_draft(&banned, &picked, game_mode, GAME_STATE_HERO_SELECTION),
+            vec![DraftEvent::TeamPick { hero_id: 2 }]
+        );
+        assert!(diff_draft(&picked, &picked, game_mode, GAME_STATE_HERO_SELECTION).is_empty());
+
+        // Players taking heroes from their team's picks are not draft events
+        let mut assigned = picked.clone();
+        assigned.player_heroes = players(&[(0, 2)]);
+        assert!(diff_draft(&picked, &assigned, game_mode, GAME_STATE_STRATEGY_TIME).is_empty());
+    }
+
+    #[test]
+    fn captains_mode() {
+        captains_mode_sequence(CAPTAINS_MODE);
+    }
+
+    #[test]
+    fn reverse_captains_mode() {
+        captains_mode_sequence(REVERSE_CAPTAINS_MODE);
+    }
+
+    #[test]
+    fn captains_draft() {
+        captains_mode_sequence(CAPTAINS_DRAFT);
+    }
+
+    #[test]
+    fn all_pick_ban_votes() {
+        let start = DraftState {
+            bans: vec![0; 16],
+            ..Default::default()
+        };
+        let mut banned = start.clone();
+        banned.bans[..3].copy_from_slice(&[5, 6, 7]);
+        assert_eq!(
+            diff_draft(&start, &banned, ALL_PICK, GAME_STATE_HERO_SELECTION),
+            vec![
+                DraftEvent::Ban { hero_id: 5 },
+                DraftEvent::Ban { hero_id: 6 },
+                DraftEvent::Ban { hero_id: 7 },
+            ]
+        );
+
+        let mut picked = banned.clone();
+        picked.player_heroes = players(&[(3, 10)]);
+        assert_eq!(
+            diff_draft(&banned, &picked, ALL_PICK, GAME_STATE_HERO_SELECTION),
+            vec![DraftEvent::PlayerPick {
+                slot: 3,
+                hero_id: 10,
+                repick: false
+            }]
+        );
+
+        let mut repicked = picked.clone();
+        repicked.player_heroes = players(&[(3, 11)]);
+        assert_eq!(
+            diff_draft(&picked, &repicked, ALL_PICK, GAME_STATE_HERO_SELECTION),
+            vec![DraftEvent::PlayerPick {
+                slot: 3,
+                hero_id: 11,
+                repick: true
+            }]
+        );
+    }
+
+    #[test]
+    fn all_pick_strategy_time_swap() {
+        let picked = DraftState {
+            player_heroes: players(&[(0, 10), (1, 20)]),
+            ..Default::default()
+        };
+        let swapped = DraftState {
+            player_heroes: players(&[(0, 20), (1, 10)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            diff_draft(&picked, &swapped, ALL_PICK, GAME_STATE_STRATEGY_TIME),
+            vec![
+                DraftEvent::Swap {
+                    slot: 0,
+                    hero_id: 20,
+                    previous: 10
+                },
+                DraftEvent::Swap {
+                    slot: 1,
+                    hero_id: 10,
+                    previous: 20
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn turbo() {
+        let start = DraftState::default();
+        let picked = DraftState {
+            player_heroes: players(&[(0, 1), (9, 2)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            diff_draft(&start, &picked, TURBO, GAME_STATE_HERO_SELECTION),
+            vec![
+                DraftEvent::PlayerPick {
+                    slot: 0,
+                    hero_id: 1,
+                    repick: false
+                },
+                DraftEvent::PlayerPick {
+                    slot: 9,
+                    hero_id: 2,
+                    repick: false
+                },
+            ]
+        );
+
+        // Random heroes handed out when strategy time starts are still first picks
+        let randomed = DraftState {
+            player_heroes: players(&[(0, 1), (5, 3), (9, 2)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            diff_draft(&picked, &randomed, TURBO, GAME_STATE_STRATEGY_TIME),
+            vec![DraftEvent::PlayerPick {
+                slot: 5,
+                hero_id: 3,
+                repick: false
+            }]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Game state transitions.
+
+use std::fmt::{Display, Formatter};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use d2_stampede::prelude::*;
+
+use crate::{App, Entry};
+
+/// `DOTA_GameState`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum GameState {
+    Init,
+    WaitForPlayersToLoad,
+    HeroSelection,
+    StrategyTime,
+    PreGame,
+    GameInProgress,
+    PostGame,
+    Disconnect,
+    TeamShowcase,
+    CustomGameSetup,
+    WaitForMapToLoad,
+    ScenarioSetup,
+    PlayerDraft,
+    Unknown(u8),
+}
+
+impl From<u8> for GameState {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => GameState::Init,
+            1 => GameState::WaitForPlayersToLoad,
+            2 => GameState::HeroSelection,
+            3 => GameState::StrategyTime,
+            4 => GameState::PreGame,
+            5 => GameState::GameInProgress,
+            6 => GameState::PostGame,
+            7 => GameState::Disconnect,
+            8 => GameState::TeamShowcase,
+            9 => GameState::CustomGameSetup,
+            10 => GameState::WaitForMapToLoad,
+            11 => GameState::ScenarioSetup,
+            12 => GameState::PlayerDraft,
+            x => GameState::Unknown(x),
+        }
+    }
+}
+
+impl Display for GameState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GameState::Init => "init",
+            GameState::WaitForPlayersToLoad => "wait_for_players_to_load",
+            GameState::HeroSelection => "hero_selection",
+            GameState::StrategyTime => "strategy_time",
+            GameState::PreGame => "pre_game",
+            GameState::GameInProgress => "game_in_progress",
+            GameState::PostGame => "post_game",
+            GameState::Disconnect => "disconnect",
+            GameState::TeamShowcase => "team_showcase",
+            GameState::CustomGameSetup => "custom_game_setup",
+            GameState::WaitForMapToLoad => "wait_for_map_to_load",
+            GameState::ScenarioSetup => "scenario_setup",
+            GameState::PlayerDraft => "player_draft",
+            GameState::Unknown(x) => return write!(f, "{}", x),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl GameState {
+    /// Game state a `game_state_change` entry switched to
+    pub fn of(entry: &Entry) -> Option<GameState> {
+        entry.stage.map(GameState::from)
+    }
+}
+
+/// Seconds from the horn to the end of the game, from the `post_game` state change or the last `interval` entry
+pub fn game_length(entries: &[Entry]) -> Option<f32> {
+    let post_game = entries
+        .iter()
+        .filter(|e| e.r#type.as_deref() == Some("game_state_change"))
+        .find(|e| GameState::of(e) == Some(GameState::PostGame));
+    let last_interval = || entries.iter().rev().find(|e| e.r#type.as_deref() == Some("interval"));
+    post_game.or_else(last_interval).map(|e| e.time)
+}
+
+impl App {
+    pub(crate) fn process_game_state(&mut self, ctx: &Context, grp: &Entity) -> Result<()> {
+        let Some(state): Option<u8> = try_property!(grp, "m_pGameRules.m_nGameState") else {
+            return Ok(());
+        };
+        let previous = self.game_state.replace(state);
+        if previous == Some(state) {
+            return Ok(());
+        }
+
+        let mut entry = Entry::new(self.time(ctx)?);
+        entry.r#type = "game_state_change".to_string().into();
+        entry.key = GameState::from(state).to_string().into();
+        entry.value = previous.map(u32::from);
+        entry.stage = state.into();
+        self.output(entry)
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+
+use d2_stampede::prelude::*;
+
+use crate::{end_time, App, Entry};
+
+const GOLD_SPENT: [&str; 4] = [
+    "m_iGoldSpentOnItems",
+    "m_iGoldSpentOnConsumables",
+    "m_iGoldSpentOnSupport",
+    "m_iGoldSpentOnBuybacks",
+];
+
+/// Release of 7.07, 2017-11-01 00:00 UTC, which based buyback cost on net worth
+const NETWORTH_BUYBACK_SINCE: u32 = 1509494400;
+
+/// Buyback cost of the patch the game was played on, `time` in seconds since the horn
+fn buyback_cost(end_time: u32, networth: u32, level: u8, time: f32) -> u32 {
+    if end_time >= NETWORTH_BUYBACK_SINCE {
+        200 + networth / 13
+    } else {
+        100 + (level as f32 * level as f32 * 1.5 + time.max(0.0) * 0.25) as u32
+    }
+}
+
+/// Buyback cost and availability of `interval` entries, the patch is only known from the `epilogue` entry at
+/// the end of the replay, games without one are assumed to be recent
+pub(crate) fn set_buyback_costs(entries: &mut [Entry]) {
+    let end_time = end_time(entries).unwrap_or(u32::MAX);
+    for entry in entries.iter_mut().filter(|e| e.r#type.as_deref() == Some("interval")) {
+        let (Some(networth), Some(level)) = (entry.networth, entry.level) else {
+            continue;
+        };
+        let cost = buyback_cost(end_time, networth, level, entry.time);
+        entry.buyback_cost = cost.into();
+        if let (Some(gold), Some(cooldown)) = (entry.current_gold, entry.buyback_cooldown) {
+            entry.can_buyback = (gold >= cost && cooldown == 0.0).into();
+        }
+    }
+}
+
+impl App {
+    /// Unspent gold, gold spent and buyback cooldown of an `interval` entry, see [`set_buyback_costs`] for
+    /// the cost
+    pub(crate) fn set_interval_gold(
+        &self,
+        ctx: &Context,
+        pr: &Entity,
+        data_team: &Entity,
+        slot: i32,
+        team_slot: i32,
+        entry: &mut Entry,
+    ) -> Result<()> {
+        let reliable: Option<u32> = try_property!(data_team, "m_vecDataTeam.{team_slot:04}.m_iReliableGold");
+        let unreliable: Option<u32> = try_property!(data_team, "m_vecDataTeam.{team_slot:04}.m_iUnreliableGold");
+        entry.reliable_gold = reliable;
+        entry.unreliable_gold = unreliable;
+        entry.current_gold = reliable.zip(unreliable).map(|(r, u)| r + u);
+        entry.gold_spent = GOLD_SPENT
+            .iter()
+            .map(|field| -> Option<u32> { try_property!(data_team, "m_vecDataTeam.{team_slot:04}.{field}") })
+            .sum::<Option<u32>>();
+
+        entry.buyback_cooldown = buyback_cooldown(pr, slot, self.server_time(ctx)?);
+        Ok(())
+    }
+}
+
+/// Seconds until the player can buy back again, `server_time` from `App::server_time`
+pub(crate) fn buyback_cooldown(pr: &Entity, slot: i32, server_time: f32) -> Option<f32> {
+    let buyback_time: f32 = try_property!(pr, "m_vecPlayerTeamData.{slot:04}.m_flBuybackCooldownTime")?;
+    Some((buyback_time - server_time).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(time: f32, networth: u32, gold: u32, cooldown: f32) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = "interval".to_string().into();
+        entry.networth = networth.into();
+        entry.level = Some(10);
+        entry.current_gold = gold.into();
+        entry.buyback_cooldown = cooldown.into();
+        entry
+    }
+
+    fn epilogue(end_time: u32) -> Entry {
+        let mut entry = Entry::new(3000.0);
+        entry.r#type = "epilogue".to_string().into();
+        entry.key = format!(r#"{{"game_info":{{"dota":{{"end_time":{end_time}}}}}}}"#).into();
+        entry
+    }
+
+    #[test]
+    fn networth_buyback() {
+        let mut entries = [
+            interval(1200.0, 13000, 1200, 0.0),
+            interval(1200.0, 13000, 1199, 0.0),
+            interval(1200.0, 13000, 5000, 10.0),
+            epilogue(1700000000),
+        ];
+        set_buyback_costs(&mut entries);
+        assert_eq!(entries[0].buyback_cost, Some(1200));
+        assert_eq!(entries[0].can_buyback, Some(true));
+        assert_eq!(entries[1].can_buyback, Some(false));
+        assert_eq!(entries[2].can_buyback, Some(false));
+        assert_eq!(entries[3].buyback_cost, None);
+    }
+
+    #[test]
+    fn legacy_buyback() {
+        let mut entries = [interval(1200.0, 13000, 1000, 0.0), epilogue(1500000000)];
+        set_buyback_costs(&mut entries);
+        assert_eq!(entries[0].buyback_cost, Some(100 + 150 + 300));
+        assert_eq!(entries[0].can_buyback, Some(true));
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+
+use d2_stampede::prelude::*;
+
+use crate::gold::buyback_cooldown;
+use crate::{schedule, App, Entry};
+
+impl App {
+    pub(crate) fn process_hero_state(&mut self, ctx: &Context, pr: &Entity) -> Result<()> {
+        let Some(period) = self.config.hero_state_interval else {
+            return Ok(());
+        };
+
+        let time = self.time(ctx)?;
+        let tick = self.game_tick(ctx)?;
+        if !schedule(&mut self.next_hero_state, tick, period) {
+            return Ok(());
+        }
+
+        let server_time = self.server_time(ctx)?;
+
+        for (i, hero) in App::heroes(ctx, pr) {
+            let mut entry = Entry::new(time);
+            entry.r#type = "hero_state".to_string().into();
+            entry.slot = (i as i32).into();
+            entry.unit = hero.class().name().to_string().into();
+            entry.targetname = self.class_to_combat_log.get(hero.class().name()).cloned();
+            entry.life_state = try_property!(hero, "m_lifeState");
+            entry.health = try_property!(hero, "m_iHealth");
+            entry.max_health = try_property!(hero, "m_iMaxHealth");
+            entry.mana = try_property!(hero, "m_flMana");
+            entry.max_mana = try_property!(hero, "m_flMaxMana");
+            entry.strength = try_property!(hero, "m_flStrengthTotal");
+            entry.agility = try_property!(hero, "m_flAgilityTotal");
+            entry.intellect = try_property!(hero, "m_flIntellectTotal");
+            entry.armor = try_property!(hero, "m_flPhysicalArmorValue");
+            entry.damage_min = try_property!(hero, "m_iDamageMin");
+            entry.damage_max = try_property!(hero, "m_iDamageMax");
+            entry.damage_bonus = try_property!(hero, "m_iDamageBonus");
+            entry.move_speed = try_property!(hero, "m_iMoveSpeed");
+            entry.buyback_cooldown = buyback_cooldown(pr, i as i32, server_time);
+            self.output(entry)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+
+use d2_stampede::prelude::*;
+
+use crate::{schedule, App, Entry};
+
+fn slot_kind(slot: u8) -> &'static str {
+    match slot {
+        0..=5 => "inventory",
+        6..=8 => "backpack",
+        9..=14 => "stash",
+        15 => "teleport",
+        _ => "neutral",
+    }
+}
+
+impl App {
+    pub(crate) fn process_inventory(&mut self, ctx: &Context, pr: &Entity) -> Result<()> {
+        let Some(period) = self.config.inventory_interval else {
+            return Ok(());
+        };
+
+        let time = self.time(ctx)?;
+        let tick = self.game_tick(ctx)?;
+        if !schedule(&mut self.next_inventory, tick, period) {
+            return Ok(());
+        }
+
+        for (i, hero) in App::heroes(ctx, pr) {
+            let Some(hero_name) = self.class_to_combat_log.get(hero.class().name()).cloned() else {
+                continue;
+            };
+
+            for item in self.get_hero_inventory(ctx, hero) {
+                let mut entry = Entry::new(time);
+                entry.r#type = "inventory".to_string().into();
+                entry.slot = (i as i32).into();
+                entry.targetname = hero_name.clone().into();
+                entry.valuename = item.id.into();
+                entry.key = slot_kind(item.slot).to_string().into();
+                entry.itemslot = item.slot.into();
+                entry.charges = item.num_charges.into();
+                entry.secondary_charges = item.num_secondary_charges.into();
+                entry.cooldown = Some(item.cooldown).filter(|&cd| cd > 0.0);
+                self.output(entry)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use hashbrown::{HashMap, HashSet};
+
+use d2_stampede::prelude::*;
+
+use crate::{App, Entry, Item};
+
+/// Seconds between an item use, sale or recipe purchase in the combat log and the item disappearing for it to
+/// count as consumed, sold or combined
+const CONSUMED_WINDOW: f32 = 1.0;
+
+#[derive(Default)]
+pub(crate) struct ItemTracker {
+    heroes: [u32; 10],
+    hero_names: [String; 10],
+    handles: [Vec<usize>; 10],
+    inventories: [Option<HashMap<usize, Item>>; 10],
+    pub(crate) ground: HashSet<usize>,
+    pub(crate) last_used: HashMap<(String, String), f32>,
+    pub(crate) last_sold: HashMap<String, f32>,
+    pub(crate) last_recipe: HashMap<String, f32>,
+}
+
+fn within_window(last: &HashMap<String, f32>, hero: &str, time: f32) -> bool {
+    last.get(hero).is_some_and(|&t| time - t <= CONSUMED_WINDOW)
+}
+
+impl App {
+    pub(crate) fn process_item_changes(&mut self, ctx: &Context, pr: &Entity) -> Result<()> {
+        let time = self.time(ctx)?;
+        let mut lost: Vec<(usize, Item)> = vec![];
+        let mut gained: Vec<(usize, Item)> = vec![];
+
+        for (i, hero) in App::heroes(ctx, pr) {
+            let Some(hero_name) = self.class_to_combat_log.get(hero.class().name()).cloned() else {
+                continue;
+            };
+
+            if self.items.heroes[i] != hero.handle() {
+                self.items.heroes[i] = hero.handle();
+                self.items.hero_names[i] = hero_name;
+                self.items.inventories[i] = None;
+            }
+
+            let handles: Vec<usize> = (0..)
+                .map_while(|slot| try_property!(hero, "m_hItems.{slot:04}"))
+                .collect();
+            if self.items.inventories[i].is_some() && handles == self.items.handles[i] {
+                continue;
+            }
+            self.items.handles[i] = handles;
+
+            let current: HashMap<usize, Item> = self
+                .get_hero_inventory(ctx, hero)
+                .into_iter()
+                .map(|item| (item.handle, item))
+                .collect();
+            let Some(previous) = self.items.inventories[i].replace(current) else {
+                continue;
+            };
+            let current = self.items.inventories[i].as_ref().unwrap();
+
+            gained.extend(
+                current
+                    .iter()
+                    .filter(|(handle, _)| !previous.contains_key(*handle))
+                    .map(|(_, item)| (i, item.clone())),
+            );
+            lost.extend(
+                previous
+                    .into_iter()
+                    .filter(|(handle, _)| !current.contains_key(handle))
+                    .map(|(_, item)| (i, item)),
+            );
+        }
+
+        // Hash map order differs between runs, sort to keep `seq` stable
+        gained.sort_by_key(|(i, item)| (*i, item.handle));
+        lost.sort_by_key(|(i, item)| (*i, item.handle));
+
+        let mut removed: Vec<(usize, Item)> = vec![];
+        for (i, item) in lost {
+            let key = (self.items.hero_names[i].clone(), item.id.clone());
+            if let Some(pos) = gained.iter().position(|(_, x)| x.handle == item.handle) {
+                let (j, _) = gained.swap_remove(pos);
+                let mut entry = self.item_entry(time, "item_given", i, &item);
+                entry.sourcename = self.items.hero_names[i].clone().into();
+                entry.targetname = self.items.hero_names[j].clone().into();
+                self.output(entry)?;
+            } else if self.items.ground.contains(&item.handle) {
+                let entry = self.item_entry(time, "item_dropped", i, &item);
+                self.output(entry)?;
+            } else if self
+                .items
+                .last_used
+                .get(&key)
+                .is_some_and(|&t| time - t <= CONSUMED_WINDOW)
+            {
+                let entry = self.item_entry(time, "item_consumed", i, &item);
+                self.output(entry)?;
+            } else {
+                removed.push((i, item));
+            }
+        }
+
+        let mut created: Vec<(usize, Item)> = vec![];
+        for (i, item) in gained {
+            if self.items.ground.remove(&item.handle) {
+                let entry = self.item_entry(time, "item_picked_up", i, &item);
+                self.output(entry)?;
+            } else {
+                created.push((i, item));
+            }
+        }
+
+        for i in 0..10 {
+            let sources: Vec<&Item> = removed.iter().filter(|(x, _)| *x == i).map(|(_, item)| item).collect();
+            let results: Vec<&Item> = created.iter().filter(|(x, _)| *x == i).map(|(_, item)| item).collect();
+            let hero = &self.items.hero_names[i];
+            let sold = within_window(&self.items.last_sold, hero, time);
+            let recipe = within_window(&self.items.last_recipe, hero, time);
+            // Plain purchases are already reported by the combat log, items created next to a sale or a removal
+            // are taken as purchases
+            match (sources.len(), results.len()) {
+                (0, _) => {}
+                _ if sold => {
+                    for item in sources {
+                        let entry = self.item_entry(time, "item_sold", i, item);
+                        self.output(entry)?;
+                    }
+                }
+                (1, n) if n > 1 => {
+                    for result in results {
+                        let mut entry = self.item_entry(time, "item_disassembled", i, sources[0]);
+                        entry.key = result.id.clone().into();
+                        self.output(entry)?;
+                    }
+                }
+                // Recipes upgrade a single item, e.g. Dagon levels
+                (1, 1) if recipe => {
+                    let mut entry = self.item_entry(time, "item_combined", i, sources[0]);
+                    entry.key = results[0].id.clone().into();
+                    self.output(entry)?;
+                }
+                (n, 1) if n > 1 => {
+                    for item in sources {
+                        let mut entry = self.item_entry(time, "item_combined", i, item);
+                        entry.key = results[0].id.clone().into();
+                        self.output(entry)?;
+                    }
+                }
+                // Courier deliveries, expired Aegis, timed out items, and changes that can't be told apart, like
+                // two combines on the same tick
+                _ => {
+                    for item in sources {
+                        let entry = self.item_entry(time, "item_removed", i, item);
+                        self.output(entry)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn item_entry(&self, time: f32, r#type: &str, i: usize, item: &Item) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = r#type.to_string().into();
+        entry.slot = (i as i32).into();
+        entry.targetname = self.items.hero_names[i].clone().into();
+        entry.valuename = item.id.clone().into();
+        entry.itemslot = item.slot.into();
+        entry.charges = item.num_charges.into();
+        entry.ehandle = (item.handle as u32).into();
+        entry
+    }
+}
@@ -0,0 +1,148 @@
+//! Hero kills with assists, bounty, position and respawn time.
+
+use std::mem;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use d2_stampede::prelude::*;
+
+use crate::map::entity_position;
+use crate::reasons::GoldReason;
+use crate::{App, Entry};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Kill {
+    pub time: f32,
+    pub victim: String,
+    pub victim_slot: i32,
+    pub killer: Option<String>,
+    pub killer_slot: Option<i32>,
+    pub inflictor: Option<String>,
+    /// Slots are player indices 0-9 like the rest of the entry slots
+    pub assist_slots: Vec<i32>,
+    /// Gold earned by the killer and assisting heroes
+    pub bounty: u32,
+    pub gold_lost: u32,
+    pub respawn_seconds: Option<i32>,
+    pub position: Option<[f32; 2]>,
+}
+
+impl Kill {
+    /// Kill described by a `kill` entry
+    pub fn of(entry: &Entry) -> Option<Kill> {
+        if entry.r#type.as_deref() != Some("kill") {
+            return None;
+        }
+        Some(Kill {
+            time: entry.precise_time(),
+            victim: entry.targetname.clone()?,
+            victim_slot: entry.target_slot?,
+            killer: entry.attackername.clone(),
+            killer_slot: entry.slot,
+            inflictor: entry.inflictor.clone(),
+            assist_slots: entry.assist_slots.clone().unwrap_or_default(),
+            bounty: entry.bounty.unwrap_or_default(),
+            gold_lost: entry.gold_lost.unwrap_or_default(),
+            respawn_seconds: entry.respawn_seconds,
+            position: entry.world_x.zip(entry.world_y).map(|(x, y)| [x, y]),
+        })
+    }
+}
+
+pub fn kill_log(entries: &[Entry]) -> Vec<Kill> {
+    entries.iter().filter_map(Kill::of).collect()
+}
+
+/// Kills wait for the bounty from the same tick and the respawn time set on the next one
+#[derive(Default)]
+pub(crate) struct KillTracker {
+    /// Kill entries and the slots already credited with bounty
+    pending: Vec<(Entry, Vec<i32>)>,
+}
+
+impl App {
+    pub(crate) fn record_kill(&mut self, ctx: &Context, cle: &CombatLogEntry) -> Result<()> {
+        if !cle.is_target_hero().unwrap_or_default() || cle.is_target_illusion().unwrap_or_default() {
+            return Ok(());
+        }
+        let Some(victim_slot) = self.get_slot_by_name(cle.target_name()) else {
+            return Ok(());
+        };
+
+        let mut entry = Entry::new(cle.timestamp()?);
+        entry.r#type = "kill".to_string().into();
+        // Output on the next tick, keep the tick of the death
+        entry.tick = ctx.tick().into();
+        entry.attackername = cle.attacker_name().ok().map(|x| x.into());
+        entry.targetname = cle.target_name().ok().map(|x| x.into());
+        entry.inflictor = cle.inflictor_name().ok().map(|x| x.into());
+        entry.slot = self
+            .get_slot_by_name(cle.attacker_name())
+            .or_else(|| self.get_slot_by_name(cle.damage_source_name()));
+        entry.target_slot = victim_slot.into();
+        entry.assist_slots = cle
+            .assist_players()
+            .ok()
+            .map(|ids| ids.iter().filter_map(|&id| self.get_slot_by_player_id(id)).collect());
+
+        let pr = ctx.entities().get_by_class_name("CDOTA_PlayerResource")?;
+        let hero_handle: usize = property!(pr, "m_vecPlayerTeamData.{victim_slot:04}.m_hSelectedHero");
+        if let Ok(hero) = ctx.entities().get_by_handle(hero_handle) {
+            entry.x = try_property!(hero, "CBodyComponent.m_cellX");
+            entry.y = try_property!(hero, "CBodyComponent.m_cellY");
+            if let Some([x, y, z]) = entity_position(hero) {
+                entry.world_x = x.into();
+                entry.world_y = y.into();
+                entry.world_z = z.into();
+            }
+        }
+
+        self.kills.pending.push((entry, vec![]));
+        Ok(())
+    }
+
+    pub(crate) fn record_kill_gold(&mut self, cle: &CombatLogEntry) -> Result<()> {
+        let (Ok(time), Ok(value), Ok(reason)) = (cle.timestamp(), cle.value(), cle.gold_reason()) else {
+            return Ok(());
+        };
+        match GoldReason::from(reason) {
+            GoldReason::HeroKill => {
+                let Some(receiver) = self.get_slot_by_name(cle.target_name()) else {
+                    return Ok(());
+                };
+                // Every kill of a multi-kill on the same tick pays each participant separately
+                if let Some((kill, credited)) = self.kills.pending.iter_mut().find(|(k, credited)| {
+                    k.time == time
+                        && !credited.contains(&receiver)
+                        && (k.slot == Some(receiver) || k.assist_slots.as_ref().is_some_and(|a| a.contains(&receiver)))
+                }) {
+                    *kill.bounty.get_or_insert(0) += value;
+                    credited.push(receiver);
+                }
+            }
+            GoldReason::Death => {
+                let victim = cle.target_name().ok();
+                if let Some((kill, _)) = self
+                    .kills
+                    .pending
+                    .iter_mut()
+                    .find(|(k, _)| k.time == time && k.targetname.as_deref() == victim && k.gold_lost.is_none())
+                {
+                    kill.gold_lost = value.into();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn process_kills(&mut self, pr: &Entity) -> Result<()> {
+        for (mut entry, _) in mem::take(&mut self.kills.pending) {
+            let slot = entry.target_slot.unwrap_or_default();
+            entry.respawn_seconds = try_property!(pr, "m_vecPlayerTeamData.{slot:04}.m_iRespawnSeconds");
+            self.output(entry)?;
+        }
+        Ok(())
+    }
+}
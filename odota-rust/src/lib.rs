@@ -1,7 +1,7 @@
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::mem;
 use std::rc::Rc;
@@ -16,6 +16,38 @@ use d2_stampede::proto::*;
 use d2_stampede_observers::game_time::*;
 use d2_stampede_observers::wards::*;
 
+pub mod actions;
+pub mod advantage;
+pub mod casts;
+pub mod chat;
+pub mod cosmetics;
+pub mod game_state;
+pub mod kills;
+pub mod map;
+pub mod modifiers;
+pub mod reasons;
+pub mod roster;
+pub mod skill_build;
+
+mod connection;
+mod draft;
+mod gold;
+mod hero_state;
+mod inventory;
+mod items;
+mod pause;
+mod position;
+
+use chat::{chat_wheel_message, ChatChannel, ChatEvent};
+use connection::ConnectionTracker;
+use cosmetics::CosmeticsTracker;
+use draft::Draft;
+use items::ItemTracker;
+use kills::KillTracker;
+use pause::PauseTracker;
+use reasons::GoldReason;
+use skill_build::SkillTracker;
+
 #[serde_with::skip_serializing_none]
 #[derive(Serialize, Deserialize, Default)]
 pub struct Entry {
@@ -86,6 +118,54 @@ pub struct Entry {
     pub draft_extime1: Option<u16>,
     pub networth: Option<u32>,
     pub stage: Option<u8>,
+    pub game_mode: Option<i32>,
+    pub cooldown: Option<f32>,
+    pub health: Option<u32>,
+    pub max_health: Option<u32>,
+    pub mana: Option<f32>,
+    pub max_mana: Option<f32>,
+    pub strength: Option<f32>,
+    pub agility: Option<f32>,
+    pub intellect: Option<f32>,
+    pub armor: Option<f32>,
+    pub damage_min: Option<u16>,
+    pub damage_max: Option<u16>,
+    pub damage_bonus: Option<u16>,
+    pub move_speed: Option<u16>,
+    pub buyback_cooldown: Option<f32>,
+    pub world_x: Option<f32>,
+    pub world_y: Option<f32>,
+    pub world_z: Option<f32>,
+    pub target_slot: Option<i32>,
+    pub queue: Option<bool>,
+    pub units: Option<Vec<String>>,
+    pub channel: Option<String>,
+    pub chat_event: Option<ChatEvent>,
+    pub tick: Option<u32>,
+    pub game_time: Option<f32>,
+    pub real_time: Option<f32>,
+    pub seq: Option<u64>,
+    pub duration: Option<f32>,
+    pub paused_time: Option<f32>,
+    pub role: Option<String>,
+    pub player_id: Option<i32>,
+    pub account_id: Option<u32>,
+    pub steam_id: Option<u64>,
+    pub style: Option<u8>,
+    pub quality: Option<u8>,
+    pub stack_count: Option<u32>,
+    pub silence_modifier: Option<bool>,
+    pub root_modifier: Option<bool>,
+    pub reliable_gold: Option<u32>,
+    pub unreliable_gold: Option<u32>,
+    pub current_gold: Option<u32>,
+    pub gold_spent: Option<u32>,
+    pub buyback_cost: Option<u32>,
+    pub can_buyback: Option<bool>,
+    pub assist_slots: Option<Vec<i32>>,
+    pub bounty: Option<u32>,
+    pub gold_lost: Option<u32>,
+    pub respawn_seconds: Option<i32>,
 }
 
 impl Display for Entry {
@@ -101,14 +181,28 @@ impl Entry {
             ..Default::default()
         }
     }
+
+    /// Seconds since the horn, unlike `time` not floored
+    pub fn precise_time(&self) -> f32 {
+        self.game_time.unwrap_or(self.time)
+    }
 }
 
-#[derive(Default)]
+/// Unix time the game ended at, from the `epilogue` entry
+pub fn end_time(entries: &[Entry]) -> Option<u32> {
+    let epilogue = entries.iter().find(|e| e.r#type.as_deref() == Some("epilogue"))?;
+    let file_info: serde_json::Value = serde_json::from_str(epilogue.key.as_deref()?).ok()?;
+    Some(file_info.pointer("/game_info/dota/end_time")?.as_u64()? as u32)
+}
+
+#[derive(Default, Clone)]
 struct Item {
     id: String,
+    handle: usize,
     slot: u8,
     num_charges: u8,
     num_secondary_charges: u8,
+    cooldown: f32,
 }
 
 #[derive(Default)]
@@ -117,38 +211,72 @@ struct Ability {
     level: u8,
 }
 
+/// Steam id of account id 0
+const STEAM_ID_BASE: u64 = 76561197960265728;
+
+/// Returns whether a family sampled every `period` seconds is due at game `tick`, scheduling its next sample if so.
+/// Periods are rounded to whole ticks so that `0.0` samples every tick.
+fn schedule(next: &mut u32, tick: u32, period: f32) -> bool {
+    if tick < *next {
+        return false;
+    }
+    let step = ((period * 30.0).round() as u32).max(1);
+    *next += (tick - *next) / step * step + step;
+    true
+}
+
 #[derive(Default)]
 struct App {
     output: Vec<Entry>,
     game_time: Rc<RefCell<GameTime>>,
-    next_interval: i32,
+    next_update: i32,
+    next_interval: u32,
     valid_indices: [i32; 10],
     init: bool,
     start_time: f32,
+    start_tick: u32,
+    tick: u32,
+    seq: u64,
     name_to_slot: HashMap<String, i32>,
     abilities_tracking: HashMap<String, u8>,
     slot_to_players_slot: HashMap<i32, i32>,
     steam_id_to_player_slot: HashMap<u64, i32>,
-    cosmetics_map: HashMap<i32, i32>,
+    cosmetics: CosmeticsTracker,
     dota_plus_xp_map: HashMap<i32, i32>,
-    ping_count: i32,
-    draft_order_processed: [bool; 24],
-    order: u8,
-    is_draft_start_time_processed: bool,
+    ping_count: u32,
+    draft: Draft,
     is_dota_plus_processed: bool,
     post_game: bool,
     is_player_starting_items_written: [bool; 10],
     class_to_combat_log: HashMap<String, String>,
     log_buffer: VecDeque<Entry>,
+    next_position: u32,
+    units: BTreeSet<u32>,
+    next_inventory: u32,
+    next_hero_state: u32,
+    items: ItemTracker,
+    pause: PauseTracker,
+    connections: ConnectionTracker,
+    game_state: Option<u8>,
+    skills: SkillTracker,
+    kills: KillTracker,
+    config: Config,
 }
 
 #[observer]
 impl App {
     #[inline(always)]
     fn output(&mut self, mut e: Entry) -> Result<()> {
+        let tick = *e.tick.get_or_insert(self.tick);
+        if e.seq.is_none() {
+            e.seq = self.seq.into();
+            self.seq += 1;
+        }
         if self.start_time == 0.0 {
             self.log_buffer.push_back(e);
         } else {
+            e.game_time = (e.time - self.start_time).into();
+            e.real_time = ((tick as f32 - self.start_tick as f32) / 30.0).into();
             e.time = (e.time - self.start_time).floor();
             self.output.push(e);
         }
@@ -165,7 +293,37 @@ impl App {
 
     #[inline(always)]
     fn time(&self, ctx: &Context) -> Result<f32> {
-        Ok(self.game_time.borrow().tick(ctx)? as f32 / 30.0)
+        Ok(self.game_tick(ctx)? as f32 / 30.0)
+    }
+
+    #[inline(always)]
+    fn game_tick(&self, ctx: &Context) -> Result<u32> {
+        self.game_time.borrow().tick(ctx)
+    }
+
+    /// Selected hero of every player index, players without a hero entity are skipped
+    fn heroes<'a>(ctx: &'a Context, pr: &'a Entity) -> impl Iterator<Item = (usize, &'a Entity)> {
+        (0..10).filter_map(move |i| {
+            let hero_handle: usize = try_property!(pr, "m_vecPlayerTeamData.{i:04}.m_hSelectedHero")?;
+            ctx.entities().get_by_handle(hero_handle).ok().map(|hero| (i, hero))
+        })
+    }
+
+    /// Entity of an index from a message, unset indices read as 0, the world entity
+    fn entity_at(ctx: &Context, index: i32) -> Option<&Entity> {
+        if index <= 0 {
+            return None;
+        }
+        ctx.entities().get_by_index(index as usize).ok()
+    }
+
+    /// Server clock that cooldown end times are expressed in, not shifted to the horn
+    #[inline(always)]
+    fn server_time(&self, ctx: &Context) -> Result<f32> {
+        Ok(property!(
+            ctx.entities().get_by_class_name("CDOTAGamerulesProxy")?,
+            "m_pGameRules.m_fGameTime"
+        ))
     }
 
     #[inline(always)]
@@ -181,8 +339,41 @@ impl App {
     }
 
     #[inline(always)]
-    fn get_hero_item(&self, ctx: &Context, hero: &Entity, idx: u8) -> Result<Item> {
+    fn get_player_slot_by_id(&self, ctx: &Context, player_id: i32) -> Result<i32> {
+        let pr = ctx.entities().get_by_class_name("CDOTA_PlayerResource")?;
+        let player_team: i32 = property!(pr, "m_vecPlayerData.{player_id:04}.m_iPlayerTeam");
+        let team_slot: i32 = property!(pr, "m_vecPlayerTeamData.{player_id:04}.m_iTeamSlot");
+        match player_team {
+            2 => Ok(team_slot),
+            3 => Ok(128 + team_slot),
+            _ => bail!("Player {} is not on Radiant or Dire", player_id),
+        }
+    }
+
+    #[inline(always)]
+    fn get_slot_by_player_id(&self, player_id: i32) -> Option<i32> {
+        self.valid_indices
+            .iter()
+            .position(|&i| i == player_id)
+            .map(|slot| slot as i32)
+    }
+
+    #[inline(always)]
+    fn get_slot_by_name<E>(&self, combat_log_name: Result<&str, E>) -> Option<i32> {
+        self.name_to_slot.get(combat_log_name.ok()?).copied()
+    }
+
+    #[inline(always)]
+    fn get_entity_name(&self, ctx: &Context, e: &Entity) -> Result<String> {
         let entity_names = ctx.string_tables().get_by_name("EntityNames")?;
+        Ok(entity_names
+            .get_row_by_index(property!(e, "m_pEntity.m_nameStringableIndex"))?
+            .key()
+            .into())
+    }
+
+    #[inline(always)]
+    fn get_hero_item(&self, ctx: &Context, hero: &Entity, idx: u8, server_time: f32) -> Result<Item> {
         let item_handle: usize = property!(hero, "m_hItems.{idx:04}");
 
         if item_handle == 0xFFFFFF {
@@ -190,27 +381,29 @@ impl App {
         }
 
         let item_entity = ctx.entities().get_by_handle(item_handle)?;
-        let item_name = entity_names
-            .get_row_by_index(property!(item_entity, "m_pEntity.m_nameStringableIndex"))?
-            .key();
+        let item_name = self.get_entity_name(ctx, item_entity)?;
+
+        let cooldown_end: f32 = try_property!(item_entity, "m_fCooldown").unwrap_or_default();
 
         Ok(Item {
-            id: item_name.into(),
+            id: item_name,
+            handle: item_handle,
             slot: idx,
             num_charges: property!(item_entity, "m_iCurrentCharges"),
             num_secondary_charges: property!(item_entity, "m_iSecondaryCharges"),
+            cooldown: (cooldown_end - server_time).max(0.0),
         })
     }
 
     #[inline(always)]
-    fn get_hero_inventory(&mut self, ctx: &Context, hero: &Entity) -> Vec<Item> {
-        let mut inventory: Vec<Item> = vec![];
-        for i in 0..8 {
-            if let Ok(item) = self.get_hero_item(ctx, hero, i) {
-                inventory.push(item)
-            }
-        }
-        inventory
+    fn get_hero_inventory(&self, ctx: &Context, hero: &Entity) -> Vec<Item> {
+        let Ok(server_time) = self.server_time(ctx) else {
+            return vec![];
+        };
+        (0..)
+            .map_while(|i| try_property!(hero, "m_hItems.{i:04}").map(|_: usize| i))
+            .filter_map(|i| self.get_hero_item(ctx, hero, i, server_time).ok())
+            .collect()
     }
 
     #[inline(always)]
@@ -226,7 +419,6 @@ impl App {
 
     #[inline(always)]
     fn get_hero_ability(&self, ctx: &Context, hero: &Entity, idx: i32) -> Result<Ability> {
-        let entity_names = ctx.string_tables().get_by_name("EntityNames")?;
         let ability_handle: usize = property!(hero, "m_hAbilities.{idx:04}");
 
         if ability_handle == 0xFFFFFF {
@@ -234,28 +426,24 @@ impl App {
         }
 
         let ability_entity = ctx.entities().get_by_handle(ability_handle)?;
-        let ability_name = entity_names
-            .get_row_by_index(property!(ability_entity, "m_pEntity.m_nameStringableIndex"))?
-            .key();
 
         Ok(Ability {
-            id: ability_name.into(),
+            id: self.get_entity_name(ctx, ability_entity)?,
             level: property!(ability_entity, "m_iLevel"),
         })
     }
 
     #[on_message]
     fn handle_demo_cmd(&mut self, ctx: &Context, file_info: CDemoFileInfo) -> ObserverResult {
-        let mut cosmetics_entry = Entry::new(self.time(ctx)?);
-        cosmetics_entry.r#type = "cosmetics".to_string().into();
-        cosmetics_entry.key = serde_json::to_string(&self.cosmetics_map)?.into();
-        self.output(cosmetics_entry)?;
+        self.process_cosmetics(ctx)?;
 
         let mut dota_plus_entry = Entry::new(self.time(ctx)?);
         dota_plus_entry.r#type = "dotaplus".to_string().into();
         dota_plus_entry.key = serde_json::to_string(&self.dota_plus_xp_map)?.into();
         self.output(dota_plus_entry)?;
 
+        self.process_roster(ctx)?;
+
         let mut epilogue_entry = Entry::new(self.time(ctx)?);
         epilogue_entry.r#type = "epilogue".to_string().into();
         epilogue_entry.key = serde_json::to_string(&file_info)?.into();
@@ -273,6 +461,28 @@ impl App {
                 entry.r#type = "actions".to_string().into();
                 entry.slot = self.get_player_slot(entity).ok();
                 entry.key = order.order_type().to_string().into();
+                entry.queue = order.queue().into();
+
+                if let Some(position) = order.position.as_ref() {
+                    entry.world_x = position.x().into();
+                    entry.world_y = position.y().into();
+                    entry.world_z = position.z().into();
+                }
+                if let Some(target) = App::entity_at(ctx, order.target_index()) {
+                    entry.targetname = self.get_entity_name(ctx, target).ok();
+                    entry.target_slot = self.get_player_slot(target).ok();
+                }
+                if let Some(ability) = App::entity_at(ctx, order.ability_index()) {
+                    entry.inflictor = self.get_entity_name(ctx, ability).ok();
+                }
+                entry.units = order
+                    .units
+                    .iter()
+                    .filter_map(|&idx| ctx.entities().get_by_index(idx as usize).ok())
+                    .filter_map(|unit| self.get_entity_name(ctx, unit).ok())
+                    .collect::<Vec<_>>()
+                    .into();
+
                 self.output(entry)?;
             }
         }
@@ -282,83 +492,53 @@ impl App {
     #[on_message]
     fn handle_ping(&mut self, ctx: &Context, location_ping: CDotaUserMsgLocationPing) -> ObserverResult {
         self.ping_count += 1;
-        if self.ping_count > 100000 {
+        if self.config.max_pings.is_some_and(|max| self.ping_count > max) {
             return Ok(());
         }
         let mut entry = Entry::new(self.time(ctx)?);
         entry.r#type = "pings".to_string().into();
         entry.slot = location_ping.player_id().into();
+
+        if let Some(ping) = location_ping.location_ping.as_ref() {
+            let (x, y) = (ping.x() as f32, ping.y() as f32);
+            entry.x = map::world_to_cell(x).map(|(cell, _)| cell);
+            entry.y = map::world_to_cell(y).map(|(cell, _)| cell);
+            entry.world_x = x.into();
+            entry.world_y = y.into();
+            entry.key = match ping.r#type() {
+                0 => "location".to_string(),
+                1 => "danger".to_string(),
+                2 => "retreat".to_string(),
+                x => x.to_string(),
+            }
+            .into();
+            if let Some(target) = App::entity_at(ctx, ping.target()) {
+                entry.targetname = self.get_entity_name(ctx, target).ok();
+                entry.target_slot = self.get_player_slot(target).ok();
+            }
+        }
+
         self.output(entry)?;
         Ok(())
     }
 
     #[on_tick_start]
     fn tick_start(&mut self, ctx: &Context) -> Result<()> {
-        if let Ok(grp) = ctx.entities().get_by_class_name("CDOTAGamerulesProxy") {
-            let draft_stage: i32 = property!(grp, "m_pGameRules.m_nGameState");
-            if draft_stage == 2 {
-                if !self.is_draft_start_time_processed {
-                    let x: u64 = property!(grp, "m_pGameRules.m_iPlayerIDsInControl");
-                    if x != 0 {
-                        let mut entry = Entry::new(self.time(ctx)?);
-                        entry.r#type = "draft_start".to_string().into();
-                        self.output(entry)?;
-                        self.is_draft_start_time_processed = true;
-                    }
-                }
-                let mut draft_heroes: [i32; 24] = [0; 24];
-                draft_heroes[0] = property!(grp, "m_pGameRules.m_BannedHeroes.0000");
-                draft_heroes[1] = property!(grp, "m_pGameRules.m_BannedHeroes.0001");
-                draft_heroes[2] = property!(grp, "m_pGameRules.m_BannedHeroes.0002");
-                draft_heroes[3] = property!(grp, "m_pGameRules.m_BannedHeroes.0003");
-                draft_heroes[4] = property!(grp, "m_pGameRules.m_BannedHeroes.0004");
-                draft_heroes[5] = property!(grp, "m_pGameRules.m_BannedHeroes.0005");
-                draft_heroes[6] = property!(grp, "m_pGameRules.m_BannedHeroes.0006");
-                draft_heroes[7] = property!(grp, "m_pGameRules.m_BannedHeroes.0007");
-                draft_heroes[8] = property!(grp, "m_pGameRules.m_BannedHeroes.0008");
-                draft_heroes[9] = property!(grp, "m_pGameRules.m_BannedHeroes.0009");
-                draft_heroes[9] = property!(grp, "m_pGameRules.m_BannedHeroes.0009");
-                draft_heroes[10] = try_property!(grp, "m_pGameRules.m_BannedHeroes.0010").unwrap_or_default();
-                draft_heroes[11] = try_property!(grp, "m_pGameRules.m_BannedHeroes.0011").unwrap_or_default();
-                draft_heroes[12] = try_property!(grp, "m_pGameRules.m_BannedHeroes.0012").unwrap_or_default();
-                draft_heroes[13] = try_property!(grp, "m_pGameRules.m_BannedHeroes.0013").unwrap_or_default();
-                draft_heroes[14] = property!(grp, "m_pGameRules.m_SelectedHeroes.0000");
-                draft_heroes[15] = property!(grp, "m_pGameRules.m_SelectedHeroes.0001");
-                draft_heroes[16] = property!(grp, "m_pGameRules.m_SelectedHeroes.0002");
-                draft_heroes[17] = property!(grp, "m_pGameRules.m_SelectedHeroes.0003");
-                draft_heroes[18] = property!(grp, "m_pGameRules.m_SelectedHeroes.0004");
-                draft_heroes[19] = property!(grp, "m_pGameRules.m_SelectedHeroes.0005");
-                draft_heroes[20] = property!(grp, "m_pGameRules.m_SelectedHeroes.0006");
-                draft_heroes[21] = property!(grp, "m_pGameRules.m_SelectedHeroes.0007");
-                draft_heroes[22] = property!(grp, "m_pGameRules.m_SelectedHeroes.0008");
-                draft_heroes[23] = property!(grp, "m_pGameRules.m_SelectedHeroes.0009");
-
-                for (i, &hero_id) in draft_heroes.iter().enumerate() {
-                    if hero_id > 0 && !self.draft_order_processed[i] {
-                        let extime0: f32 = property!(grp, "m_pGameRules.m_fExtraTimeRemaining.0000");
-                        let extime1: f32 = property!(grp, "m_pGameRules.m_fExtraTimeRemaining.0001");
+        self.tick = ctx.tick();
 
-                        let mut entry = Entry::new(self.time(ctx)?);
-                        entry.r#type = "draft_timings".to_string().into();
-                        entry.draft_order = self.order.into();
-                        entry.pick = (i >= 14).into();
-                        entry.hero_id = draft_heroes[i].into();
-                        entry.draft_extime0 = (extime0.round() as u16).into();
-                        entry.draft_extime1 = (extime1.round() as u16).into();
-                        entry.draft_active_team = try_property!(grp, "m_pGameRules.m_iActiveTeam");
-                        self.output(entry)?;
-
-                        self.order += 1;
-                        self.draft_order_processed[i] = true;
-                    }
-                }
-            }
-            if self.next_interval == 0 {
-                self.next_interval = self.time(ctx)? as i32;
+        if let Ok(grp) = ctx.entities().get_by_class_name("CDOTAGamerulesProxy") {
+            self.process_game_state(ctx, grp)?;
+            self.process_draft(ctx, grp)?;
+            self.process_pause(ctx, grp)?;
+            if self.next_update == 0 {
+                self.next_update = self.time(ctx)? as i32;
+                self.next_interval = self.next_update as u32 * 30;
             }
         }
 
         if let Ok(pr) = ctx.entities().get_by_class_name("CDOTA_PlayerResource") {
+            self.process_kills(pr)?;
+
             if !self.init {
                 let mut added = 0;
                 let mut i = 0;
@@ -394,7 +574,16 @@ impl App {
                 }
             }
 
-            if self.init && !self.post_game && self.time(ctx)? as i32 >= self.next_interval {
+            let time = self.time(ctx)?;
+            let game_tick = self.game_tick(ctx)?;
+            let update = self.init && !self.post_game && time as i32 >= self.next_update;
+            let sample = self.init
+                && !self.post_game
+                && self
+                    .config
+                    .interval
+                    .is_some_and(|period| schedule(&mut self.next_interval, game_tick, period));
+            if update || sample {
                 for i in 0..10 {
                     let hero_id: i32 = property!(pr, "m_vecPlayerTeamData.{i:04}.m_nSelectedHeroID");
                     let hero_handle: usize = property!(pr, "m_vecPlayerTeamData.{i:04}.m_hSelectedHero");
@@ -410,6 +599,7 @@ impl App {
                     let mut entry = Entry::new(self.time(ctx)?);
                     entry.r#type = "interval".to_string().into();
                     entry.slot = i.into();
+                    entry.team = player_team.into();
                     entry.repicked = try_property!(pr, "m_vecPlayerTeamData.{i:04}.m_bHasRepicked");
                     entry.randomed = try_property!(pr, "m_vecPlayerTeamData.{i:04}.m_bHasRandomed");
                     entry.pred_vict = try_property!(pr, "m_vecPlayerTeamData.{i:04}.m_bHasPredictedVictory");
@@ -439,14 +629,20 @@ impl App {
                         entry.lh = try_property!(data_team, "m_vecDataTeam.{team_slot:04}.m_iLastHitCount");
                         entry.xp = try_property!(data_team, "m_vecDataTeam.{team_slot:04}.m_iTotalEarnedXP");
                         entry.stuns = try_property!(data_team, "m_vecDataTeam.{team_slot:04}.m_fStuns");
+                        self.set_interval_gold(ctx, pr, data_team, i, team_slot, &mut entry)?;
 
                         if let Ok(hero) = ctx.entities().get_by_handle(hero_handle) {
                             entry.x = try_property!(hero, "CBodyComponent.m_cellX");
                             entry.y = try_property!(hero, "CBodyComponent.m_cellY");
+                            if let Some([x, y, z]) = map::entity_position(hero) {
+                                entry.world_x = x.into();
+                                entry.world_y = y.into();
+                                entry.world_z = z.into();
+                            }
                             entry.unit = hero.class().name().to_string().into();
                             entry.hero_id = hero_id.into();
                             entry.life_state = try_property!(hero, "m_lifeState");
-                            if hero_id > 0 {
+                            if update && hero_id > 0 {
                                 let class = hero.class().name();
 
                                 if !self.class_to_combat_log.contains_key(class) {
@@ -470,25 +666,43 @@ impl App {
                                 }
 
                                 let hero_name = self.class_to_combat_log[class].clone();
+                                let hero_level = entry.level;
 
-                                for ability in self.get_hero_abilities(ctx, hero) {
+                                let abilities = self.get_hero_abilities(ctx, hero);
+                                self.detect_innate_abilities(hero, &hero_name, &abilities);
+
+                                let mut talents = 0;
+                                for ability in abilities {
+                                    let tier = skill_build::talent_tier(&ability.id, talents);
+                                    if tier.is_some() {
+                                        talents += 1;
+                                    }
                                     let key = hero_name.clone() + ability.id.as_str();
-                                    if !self.abilities_tracking.contains_key(&key)
-                                        || self.abilities_tracking[&key] != ability.level
-                                    {
+                                    let kind = if tier.is_some() {
+                                        "talent"
+                                    } else if self.skills.innate.contains(&key) {
+                                        "innate"
+                                    } else {
+                                        "ability"
+                                    };
+                                    if self.abilities_tracking.get(&key) != Some(&ability.level) {
                                         let mut entry = Entry::new(self.time(ctx)?);
                                         entry.r#type = "DotaAbilityLevel".to_string().into();
                                         entry.targetname = hero_name.clone().into();
                                         entry.valuename = ability.id.clone().into();
                                         entry.abilitylevel = ability.level.into();
-                                        self.abilities_tracking
-                                            .insert(hero_name.to_owned() + &ability.id, ability.level);
+                                        entry.slot = i.into();
+                                        entry.level = hero_level;
+                                        entry.key = kind.to_string().into();
+                                        entry.value = tier.map(u32::from);
                                         self.output(entry)?;
                                     }
+                                    self.abilities_tracking.insert(key, ability.level);
                                 }
+                                self.process_facet(ctx, pr, i, &hero_name)?;
 
                                 if self.time(ctx)? as i32 - self.start_time as i32 == 1 {
-                                    for item in self.get_hero_inventory(ctx, hero) {
+                                    for item in self.get_hero_inventory(ctx, hero).into_iter().filter(|x| x.slot < 8) {
                                         let mut starting_items = Entry::new(self.time(ctx)?);
                                         starting_items.r#type = "StartingItems".to_string().into();
                                         starting_items.targetname = hero_name.clone().into();
@@ -506,7 +720,7 @@ impl App {
 
                                 if !self.is_player_starting_items_written[entry.slot.unwrap() as usize] {
                                     self.is_player_starting_items_written[entry.slot.unwrap() as usize] = true;
-                                    for item in self.get_hero_inventory(ctx, hero) {
+                                    for item in self.get_hero_inventory(ctx, hero).into_iter().filter(|x| x.slot < 8) {
                                         let mut starting_items = Entry::new(self.time(ctx)?);
                                         starting_items.r#type = "DotaCombatlogPurchase".to_string().into();
                                         starting_items.targetname = hero_name.clone().into();
@@ -522,9 +736,25 @@ impl App {
                             }
                         }
                     }
-                    self.output(entry)?;
+                    if sample {
+                        self.output(entry)?;
+                    }
                 }
-                self.next_interval += 1;
+                if update {
+                    self.next_update += 1;
+                }
+            }
+
+            if self.init && !self.post_game {
+                self.process_positions(ctx, pr)?;
+                self.process_inventory(ctx, pr)?;
+                self.process_hero_state(ctx, pr)?;
+                self.process_item_changes(ctx, pr)?;
+                self.process_connections(ctx, pr)?;
+            }
+
+            if self.post_game {
+                self.process_connection_summary(ctx)?;
             }
 
             if self.post_game && !self.is_dota_plus_processed {
@@ -544,21 +774,26 @@ impl App {
     }
 
     #[on_entity]
-    fn on_entity(&mut self, _ctx: &Context, event: EntityEvents, entity: &Entity) -> Result<()> {
+    fn on_entity(&mut self, ctx: &Context, event: EntityEvents, entity: &Entity) -> Result<()> {
+        if event == EntityEvents::Created && entity.class().name() == "CDOTA_Item_Physical" {
+            let item_handle: usize = property!(entity, "m_hItem");
+            self.items.ground.insert(item_handle);
+        }
         if event == EntityEvents::Created && entity.class().name() == "CDOTAWearableItem" {
-            let account_id: u64 = property!(entity, "m_iAccountID");
-            let item_definition_idx: i32 = property!(entity, "m_iItemDefinitionIndex");
-            if account_id > 0 {
-                let account_id64: u64 = 76561197960265728 + account_id;
-                let player_slot: i32 = *self.steam_id_to_player_slot.get(&account_id64).unwrap_or(&0);
-                self.cosmetics_map.insert(item_definition_idx, player_slot);
-            }
+            self.record_wearable(ctx, entity)?;
+        }
+        if position::is_unit(entity.class().name()) {
+            match event {
+                EntityEvents::Created => self.units.insert(entity.index()),
+                EntityEvents::Deleted => self.units.remove(&entity.index()),
+                _ => false,
+            };
         }
         Ok(())
     }
 
     #[on_combat_log]
-    fn handle_cle(&mut self, _ctx: &Context, cle: &CombatLogEntry) -> Result<()> {
+    fn handle_cle(&mut self, ctx: &Context, cle: &CombatLogEntry) -> Result<()> {
         let time = cle.timestamp()?;
         let mut entry = Entry::new(time);
         entry.r#type = format!("{:?}", cle.r#type()).into();
@@ -576,19 +811,84 @@ impl App {
 
         if cle.r#type() == DotaCombatlogTypes::DotaCombatlogPurchase {
             entry.valuename = cle.value_name().ok().map(|x| x.into());
+            if let (Ok(hero), Ok(item), Ok(time)) = (cle.target_name(), cle.value_name(), self.time(ctx)) {
+                if item.starts_with("item_recipe_") {
+                    self.items.last_recipe.insert(hero.into(), time);
+                }
+            }
         }
         if cle.r#type() == DotaCombatlogTypes::DotaCombatlogGold {
             entry.gold_reason = cle.gold_reason().ok();
+            entry.slot = self.get_slot_by_name(cle.target_name());
+            self.record_kill_gold(cle)?;
+            if let (Ok(hero), Some(GoldReason::SellItem), Ok(time)) =
+                (cle.target_name(), GoldReason::of(&entry), self.time(ctx))
+            {
+                self.items.last_sold.insert(hero.into(), time);
+            }
         }
         if cle.r#type() == DotaCombatlogTypes::DotaCombatlogXp {
             entry.xp_reason = cle.xp_reason().ok();
+            entry.slot = self.get_slot_by_name(cle.target_name());
+        }
+
+        if cle.r#type() == DotaCombatlogTypes::DotaCombatlogAbility
+            || cle.r#type() == DotaCombatlogTypes::DotaCombatlogItem
+        {
+            entry.slot = self.get_slot_by_name(cle.attacker_name());
+            entry.target_slot = self.get_slot_by_name(cle.target_name());
+            entry.abilitylevel = cle
+                .ability_level()
+                .ok()
+                .filter(|&level| level > 0)
+                .map(|level| level as u8);
+            if let (Ok(x), Ok(y)) = (cle.location_x(), cle.location_y()) {
+                if x != 0.0 || y != 0.0 {
+                    entry.world_x = x.into();
+                    entry.world_y = y.into();
+                }
+            }
+        }
+
+        if matches!(
+            cle.r#type(),
+            DotaCombatlogTypes::DotaCombatlogModifierAdd
+                | DotaCombatlogTypes::DotaCombatlogModifierRemove
+                | DotaCombatlogTypes::DotaCombatlogModifierStackEvent
+        ) {
+            entry.targetillusion = cle.is_target_illusion().ok();
+            entry.slot = self.get_slot_by_name(cle.attacker_name());
+            if entry.targetillusion != Some(true) {
+                entry.target_slot = self.get_slot_by_name(cle.target_name());
+            }
+            entry.duration = cle.modifier_duration().ok().filter(|&duration| duration > 0.0);
+            entry.stack_count = cle.stack_count().ok().filter(|&stacks| stacks > 0);
+            entry.silence_modifier = cle.silence_modifier().ok().filter(|&x| x);
+            entry.root_modifier = cle.root_modifier().ok().filter(|&x| x);
+        }
+
+        if cle.r#type() == DotaCombatlogTypes::DotaCombatlogItem {
+            if let (Ok(hero), Ok(item), Ok(time)) = (cle.attacker_name(), cle.inflictor_name(), self.time(ctx)) {
+                self.items.last_used.insert((hero.into(), item.into()), time);
+            }
+        }
+
+        if cle.r#type() == DotaCombatlogTypes::DotaCombatlogModifierAdd {
+            self.process_upgrade(cle)?;
+        }
+
+        if cle.r#type() == DotaCombatlogTypes::DotaCombatlogDeath {
+            self.record_kill(ctx, cle)?;
         }
 
         if cle.r#type() == DotaCombatlogTypes::DotaCombatlogGameState && cle.value()? == 6 {
             self.post_game = true;
         }
 
-        if cle.r#type() as u32 <= 19 {
+        if cle.r#type() as u32 <= 19
+            || (self.config.modifier_stack_events
+                && cle.r#type() == DotaCombatlogTypes::DotaCombatlogModifierStackEvent)
+        {
             self.output(entry)?;
         }
 
@@ -602,6 +902,11 @@ impl App {
         entry.player1 = event.playerid_1().into();
         entry.player2 = event.playerid_2().into();
         entry.value = event.value().into();
+        let chat_event = ChatEvent::decode(&event, |id| self.get_slot_by_player_id(id));
+        if let ChatEvent::Paused { player } = chat_event {
+            self.pause.player = player.map(|player| (ctx.tick(), player));
+        }
+        entry.chat_event = chat_event.into();
         self.output(entry)
     }
     
@@ -614,6 +919,8 @@ impl App {
             event.channel_type().to_string().into()
         };
         entry.slot = event.source_player_id().into();
+        entry.player_slot = self.get_player_slot_by_id(ctx, event.source_player_id()).ok();
+        entry.channel = ChatChannel::from(event.channel_type()).to_string().into();
         entry.key = event.message_text().to_string().into();
         self.output(entry)
     }
@@ -623,15 +930,20 @@ impl App {
         let mut entry = Entry::new(self.time(ctx)?);
         entry.r#type = "chatwheel".to_string().into();
         entry.slot = event.player_id().into();
+        entry.player_slot = self.get_player_slot_by_id(ctx, event.player_id()).ok();
         entry.key = event.chat_message_id().to_string().into();
+        entry.valuename = chat_wheel_message(event.chat_message_id())
+            .map(|x| x.to_string())
+            .or_else(|| self.config.chat_wheel_sounds.get(&event.chat_message_id()).cloned());
         self.output(entry)
     }
 }
 
 
 impl GameTimeObserver for App {
-    fn on_game_started(&mut self, _ctx: &Context, start_time: f32) -> Result<()> {
+    fn on_game_started(&mut self, ctx: &Context, start_time: f32) -> Result<()> {
         self.start_time = start_time;
+        self.start_tick = ctx.tick();
         self.flush_log_buffer()
     }
 }
@@ -653,6 +965,11 @@ impl WardsObserver for App {
         entry.x = x.into();
         entry.y = y.into();
         entry.z = z.into();
+        if let Some([x, y, z]) = map::entity_position(ward) {
+            entry.world_x = x.into();
+            entry.world_y = y.into();
+            entry.world_z = z.into();
+        }
 
         let owner_handle: usize = property!(ward, "m_hOwnerEntity");
         if let Ok(owner) = ctx.entities().get_by_handle(owner_handle) {
@@ -669,7 +986,49 @@ impl WardsObserver for App {
     }
 }
 
+#[derive(Clone)]
+pub struct Config {
+    /// Seconds between `interval` entries, `0.0` samples every tick, disabled if `None`
+    pub interval: Option<f32>,
+    /// Maximum number of `pings` entries, unlimited if `None`
+    pub max_pings: Option<u32>,
+    /// Seconds between hero `position` entries, disabled if `None`
+    pub position_interval: Option<f32>,
+    /// Also sample non-hero units like couriers, summons and creeps into `position` entries at `position_interval`
+    pub unit_positions: bool,
+    /// Seconds between `inventory` snapshots, disabled if `None`
+    pub inventory_interval: Option<f32>,
+    /// Seconds between `hero_state` entries, disabled if `None`
+    pub hero_state_interval: Option<f32>,
+    /// Output `DotaCombatlogModifierStackEvent` entries, needed for stack changes in the modifier timeline
+    pub modifier_stack_events: bool,
+    /// Names of sound wheel ids for `chatwheel` entries, e.g. from the game's chat wheel data, text phrases are
+    /// built in
+    pub chat_wheel_sounds: HashMap<u32, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            interval: Some(1.0),
+            max_pings: Some(100000),
+            position_interval: None,
+            unit_positions: false,
+            inventory_interval: None,
+            hero_state_interval: None,
+            modifier_stack_events: false,
+            chat_wheel_sounds: HashMap::new(),
+        }
+    }
+}
+
+/// Entries are sorted by `time`, then `tick`, then `seq`, the order in which the parser produced them
 pub fn parse_replay(binary: &[u8]) -> Result<Vec<Entry>> {
+    parse_replay_with_config(binary, &Config::default())
+}
+
+/// Same as [`parse_replay`] with non-default [`Config`]
+pub fn parse_replay_with_config(binary: &[u8], config: &Config) -> Result<Vec<Entry>> {
     let mut parser = Parser::new(binary)?;
 
     let game_time = parser.register_observer::<GameTime>();
@@ -677,18 +1036,57 @@ pub fn parse_replay(binary: &[u8]) -> Result<Vec<Entry>> {
     let app = parser.register_observer::<App>();
 
     app.borrow_mut().game_time = game_time.clone();
+    app.borrow_mut().config = config.clone();
 
     game_time.borrow_mut().register_observer(app.clone());
     wards.borrow_mut().register_observer(app.clone());
 
     parser.run_to_end()?;
 
-    let x = mem::take(&mut app.borrow_mut().output);
+    let mut x = mem::take(&mut app.borrow_mut().output);
+    x.sort_by(|a, b| {
+        a.time
+            .total_cmp(&b.time)
+            .then(a.tick.cmp(&b.tick))
+            .then(a.seq.cmp(&b.seq))
+    });
+    gold::set_buyback_costs(&mut x);
 
     Ok(x)
 }
 
 pub mod prelude {
     pub use crate::parse_replay;
+    pub use crate::parse_replay_with_config;
+    pub use crate::Config;
     pub use crate::Entry;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(period: f32, ticks: u32) -> Vec<u32> {
+        let mut next = 0;
+        (0..ticks).filter(|&tick| schedule(&mut next, tick, period)).collect()
+    }
+
+    #[test]
+    fn schedule_every_tick() {
+        assert_eq!(samples(0.0, 3000).len(), 3000);
+    }
+
+    #[test]
+    fn schedule_period() {
+        assert_eq!(samples(1.0, 91), vec![0, 30, 60, 90]);
+        assert_eq!(samples(0.1, 10), vec![0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn schedule_skipped_ticks() {
+        let mut next = 0;
+        assert!(schedule(&mut next, 0, 1.0));
+        assert!(schedule(&mut next, 95, 1.0));
+        assert_eq!(next, 120);
+    }
+}
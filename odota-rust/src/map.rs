@@ -0,0 +1,162 @@
+//! Conversions between entity cells, world units and minimap coordinates.
+
+use d2_stampede::prelude::*;
+
+use crate::{end_time, Entry};
+
+/// World units covered by a single `CBodyComponent` cell
+pub const CELL_WIDTH: f32 = 128.0;
+
+/// Offset between cell origin and world origin
+pub const MAX_COORD: f32 = 16384.0;
+
+/// Playable area of the map in world units
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MapBounds {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+impl MapBounds {
+    /// Map since 7.33
+    pub const CURRENT: MapBounds = MapBounds {
+        min_x: -10240.0,
+        min_y: -10240.0,
+        max_x: 10240.0,
+        max_y: 10240.0,
+    };
+
+    /// Map before 7.33
+    pub const LEGACY: MapBounds = MapBounds {
+        min_x: -8288.0,
+        min_y: -8288.0,
+        max_x: 8288.0,
+        max_y: 8288.0,
+    };
+}
+
+impl MapBounds {
+    /// Release of 7.33, 2023-04-20 00:00 UTC
+    pub const CURRENT_SINCE: u32 = 1681948800;
+
+    /// Bounds of the map a game ending at `end_time` (unix seconds) was played on
+    pub fn for_end_time(end_time: u32) -> MapBounds {
+        if end_time >= MapBounds::CURRENT_SINCE {
+            MapBounds::CURRENT
+        } else {
+            MapBounds::LEGACY
+        }
+    }
+
+    /// Bounds of the replay's map, from the end time in the `epilogue` entry
+    pub fn of(entries: &[Entry]) -> Option<MapBounds> {
+        end_time(entries).map(MapBounds::for_end_time)
+    }
+}
+
+impl Default for MapBounds {
+    fn default() -> Self {
+        MapBounds::CURRENT
+    }
+}
+
+pub fn cell_to_world(cell: u8, offset: f32) -> f32 {
+    cell as f32 * CELL_WIDTH + offset - MAX_COORD
+}
+
+/// Splits world coordinate into cell and offset inside the cell, `None` outside the cell grid
+pub fn world_to_cell(world: f32) -> Option<(u8, f32)> {
+    let shifted = world + MAX_COORD;
+    let cell = (shifted / CELL_WIDTH).floor();
+    if !(0.0..=u8::MAX as f32).contains(&cell) {
+        return None;
+    }
+    Some((cell as u8, shifted - cell * CELL_WIDTH))
+}
+
+/// Normalizes world coordinates to `0.0..=1.0` with the origin in the top left corner of the minimap
+pub fn world_to_minimap(x: f32, y: f32, bounds: &MapBounds) -> (f32, f32) {
+    (
+        (x - bounds.min_x) / (bounds.max_x - bounds.min_x),
+        (bounds.max_y - y) / (bounds.max_y - bounds.min_y),
+    )
+}
+
+pub fn minimap_to_world(x: f32, y: f32, bounds: &MapBounds) -> (f32, f32) {
+    (
+        bounds.min_x + x * (bounds.max_x - bounds.min_x),
+        bounds.max_y - y * (bounds.max_y - bounds.min_y),
+    )
+}
+
+pub fn cell_to_minimap(cell_x: u8, cell_y: u8, bounds: &MapBounds) -> (f32, f32) {
+    let half_cell = CELL_WIDTH / 2.0;
+    world_to_minimap(
+        cell_to_world(cell_x, half_cell),
+        cell_to_world(cell_y, half_cell),
+        bounds,
+    )
+}
+
+/// Precise world position of an entity with `CBodyComponent`
+pub fn entity_position(e: &Entity) -> Option<[f32; 3]> {
+    let cell_x: u8 = try_property!(e, "CBodyComponent.m_cellX")?;
+    let cell_y: u8 = try_property!(e, "CBodyComponent.m_cellY")?;
+    let cell_z: u8 = try_property!(e, "CBodyComponent.m_cellZ")?;
+    let vec_x: f32 = try_property!(e, "CBodyComponent.m_vecX")?;
+    let vec_y: f32 = try_property!(e, "CBodyComponent.m_vecY")?;
+    let vec_z: f32 = try_property!(e, "CBodyComponent.m_vecZ")?;
+    Some([
+        cell_to_world(cell_x, vec_x),
+        cell_to_world(cell_y, vec_y),
+        cell_to_world(cell_z, vec_z),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_round_trip() {
+        for world in [-10240.0, -8288.5, -64.0, 0.0, 127.9, 5000.25, 10240.0] {
+            let (cell, offset) = world_to_cell(world).unwrap();
+            assert!((0.0..CELL_WIDTH).contains(&offset));
+            assert!((cell_to_world(cell, offset) - world).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn cell_out_of_range() {
+        assert_eq!(world_to_cell(-MAX_COORD - 1.0), None);
+        assert_eq!(world_to_cell(MAX_COORD + 256.0 * CELL_WIDTH), None);
+        assert_eq!(world_to_cell(-MAX_COORD), Some((0, 0.0)));
+    }
+
+    #[test]
+    fn minimap_round_trip() {
+        for bounds in [MapBounds::CURRENT, MapBounds::LEGACY] {
+            assert_eq!(world_to_minimap(bounds.min_x, bounds.max_y, &bounds), (0.0, 0.0));
+            assert_eq!(world_to_minimap(bounds.max_x, bounds.min_y, &bounds), (1.0, 1.0));
+            for (x, y) in [(-3000.0, 4500.0), (0.0, 0.0), (7000.0, -120.5)] {
+                let (mx, my) = world_to_minimap(x, y, &bounds);
+                let (wx, wy) = minimap_to_world(mx, my, &bounds);
+                assert!((wx - x).abs() < 0.01 && (wy - y).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn bounds_by_end_time() {
+        assert_eq!(MapBounds::for_end_time(1681948799), MapBounds::LEGACY);
+        assert_eq!(MapBounds::for_end_time(1681948800), MapBounds::CURRENT);
+
+        let mut epilogue = Entry::new(0.0);
+        epilogue.r#type = "epilogue".to_string().into();
+        epilogue.key = r#"{"game_info":{"dota":{"end_time":1650000000}}}"#.to_string().into();
+        assert_eq!(MapBounds::of(&[epilogue]), Some(MapBounds::LEGACY));
+        assert_eq!(MapBounds::of(&[]), None);
+    }
+}
@@ -0,0 +1,235 @@
+//! Buff and debuff intervals from paired modifier combat log entries.
+
+use hashbrown::HashMap;
+use serde::Serialize;
+
+use crate::Entry;
+
+pub const BKB: &str = "modifier_black_king_bar_immune";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Disable {
+    Stun,
+    Silence,
+    Root,
+    Hex,
+}
+
+fn is_hex(modifier: &str) -> bool {
+    modifier.contains("sheepstick") || modifier.contains("voodoo") || modifier.contains("hex")
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModifierInterval {
+    pub name: String,
+    pub unit: String,
+    pub unit_slot: Option<i32>,
+    pub caster: Option<String>,
+    pub caster_slot: Option<i32>,
+    pub start: f32,
+    /// End of the replay when the modifier was never removed
+    pub end: f32,
+    /// Duration announced when the modifier was applied
+    pub duration: Option<f32>,
+    /// Stack count changes as `(time, stacks)`, only the initial count unless parsed with
+    /// [`Config::modifier_stack_events`](crate::Config::modifier_stack_events)
+    pub stacks: Vec<(f32, u32)>,
+    pub disables: Vec<Disable>,
+}
+
+impl ModifierInterval {
+    pub fn length(&self) -> f32 {
+        self.end - self.start
+    }
+}
+
+/// Pairs `DotaCombatlogModifierAdd` with the matching `DotaCombatlogModifierRemove`, reapplying
+/// a modifier that is still active extends the open interval
+pub fn modifier_timeline(entries: &[Entry]) -> Vec<ModifierInterval> {
+    let mut timeline: Vec<ModifierInterval> = vec![];
+    let mut open: HashMap<(&str, bool, &str, Option<&str>), usize> = HashMap::new();
+    let last = entries.iter().map(Entry::precise_time).fold(0.0, f32::max);
+
+    for entry in entries {
+        let (Some(r#type), Some(unit), Some(name)) = (
+            entry.r#type.as_deref(),
+            entry.targetname.as_deref(),
+            entry.inflictor.as_deref(),
+        ) else {
+            continue;
+        };
+        let key = (
+            unit,
+            entry.targetillusion == Some(true),
+            name,
+            entry.attackername.as_deref(),
+        );
+        match r#type {
+            "DotaCombatlogModifierAdd" => {
+                if open.contains_key(&key) {
+                    continue;
+                }
+                let mut disables = vec![];
+                if entry.stun_duration.is_some() {
+                    disables.push(Disable::Stun);
+                }
+                if entry.silence_modifier == Some(true) {
+                    disables.push(Disable::Silence);
+                }
+                if entry.root_modifier == Some(true) {
+                    disables.push(Disable::Root);
+                }
+                if is_hex(name) {
+                    disables.push(Disable::Hex);
+                }
+                open.insert(key, timeline.len());
+                timeline.push(ModifierInterval {
+                    name: name.to_string(),
+                    unit: unit.to_string(),
+                    unit_slot: entry.target_slot,
+                    caster: entry.attackername.clone(),
+                    caster_slot: entry.slot,
+                    start: entry.precise_time(),
+                    end: last,
+                    duration: entry.duration,
+                    stacks: entry
+                        .stack_count
+                        .map(|s| (entry.precise_time(), s))
+                        .into_iter()
+                        .collect(),
+                    disables,
+                });
+            }
+            "DotaCombatlogModifierRemove" => {
+                if let Some(i) = open.remove(&key) {
+                    timeline[i].end = entry.precise_time();
+                }
+            }
+            "DotaCombatlogModifierStackEvent" => {
+                if let (Some(&i), Some(stacks)) = (open.get(&key), entry.stack_count) {
+                    timeline[i].stacks.push((entry.precise_time(), stacks));
+                }
+            }
+            _ => {}
+        }
+    }
+    timeline
+}
+
+/// Total time covered by `intervals`, overlaps are counted once
+pub fn uptime<'a>(intervals: impl IntoIterator<Item = &'a ModifierInterval>) -> f32 {
+    let mut spans: Vec<(f32, f32)> = intervals.into_iter().map(|m| (m.start, m.end)).collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut total = 0.0;
+    let mut covered = f32::MIN;
+    for (start, end) in spans {
+        let start = start.max(covered);
+        if end > start {
+            total += end - start;
+            covered = end;
+        }
+    }
+    total
+}
+
+/// Time a hero spent under `disable`
+pub fn disable_time(timeline: &[ModifierInterval], slot: i32, disable: Disable) -> f32 {
+    uptime(
+        timeline
+            .iter()
+            .filter(|m| m.unit_slot == Some(slot) && m.disables.contains(&disable)),
+    )
+}
+
+/// Time a hero spent with the `modifier`, e.g. [`BKB`]
+pub fn modifier_uptime(timeline: &[ModifierInterval], slot: i32, modifier: &str) -> f32 {
+    uptime(
+        timeline
+            .iter()
+            .filter(|m| m.unit_slot == Some(slot) && m.name == modifier),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifier(time: f32, r#type: &str, illusion: bool) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = r#type.to_string().into();
+        entry.targetname = "npc_dota_hero_axe".to_string().into();
+        entry.targetillusion = illusion.into();
+        entry.inflictor = "modifier_stunned".to_string().into();
+        entry.attackername = "npc_dota_hero_lion".to_string().into();
+        entry
+    }
+
+    fn add(time: f32) -> Entry {
+        modifier(time, "DotaCombatlogModifierAdd", false)
+    }
+
+    fn remove(time: f32) -> Entry {
+        modifier(time, "DotaCombatlogModifierRemove", false)
+    }
+
+    fn spans(timeline: &[ModifierInterval]) -> Vec<(f32, f32)> {
+        timeline.iter().map(|m| (m.start, m.end)).collect()
+    }
+
+    fn span(start: f32, end: f32) -> ModifierInterval {
+        ModifierInterval {
+            name: BKB.to_string(),
+            unit: "npc_dota_hero_axe".to_string(),
+            unit_slot: Some(0),
+            caster: None,
+            caster_slot: None,
+            start,
+            end,
+            duration: None,
+            stacks: vec![],
+            disables: vec![],
+        }
+    }
+
+    #[test]
+    fn readd_while_open_extends() {
+        let timeline = modifier_timeline(&[add(10.0), add(12.0), remove(15.0), add(20.0), remove(21.0)]);
+        assert_eq!(spans(&timeline), vec![(10.0, 15.0), (20.0, 21.0)]);
+    }
+
+    #[test]
+    fn remove_without_add() {
+        let timeline = modifier_timeline(&[remove(5.0), add(10.0), remove(11.0)]);
+        assert_eq!(spans(&timeline), vec![(10.0, 11.0)]);
+    }
+
+    #[test]
+    fn never_removed_lasts_until_the_end() {
+        let mut end = Entry::new(100.0);
+        end.r#type = "epilogue".to_string().into();
+        let timeline = modifier_timeline(&[add(10.0), end]);
+        assert_eq!(spans(&timeline), vec![(10.0, 100.0)]);
+    }
+
+    #[test]
+    fn illusions_are_separate() {
+        let timeline = modifier_timeline(&[
+            add(10.0),
+            modifier(11.0, "DotaCombatlogModifierAdd", true),
+            modifier(12.0, "DotaCombatlogModifierRemove", true),
+            remove(14.0),
+        ]);
+        assert_eq!(spans(&timeline), vec![(10.0, 14.0), (11.0, 12.0)]);
+    }
+
+    #[test]
+    fn uptime_counts_overlaps_once() {
+        let intervals = [span(0.0, 10.0), span(5.0, 15.0), span(2.0, 4.0), span(20.0, 25.0)];
+        assert_eq!(uptime(&intervals), 20.0);
+        assert_eq!(modifier_uptime(&intervals, 0, BKB), 20.0);
+        assert_eq!(modifier_uptime(&intervals, 1, BKB), 0.0);
+        assert_eq!(uptime(&[]), 0.0);
+    }
+}
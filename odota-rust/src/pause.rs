@@ -0,0 +1,45 @@
+use anyhow::Result;
+
+use d2_stampede::prelude::*;
+
+use crate::{App, Entry};
+
+#[derive(Default)]
+pub(crate) struct PauseTracker {
+    paused: bool,
+    start_tick: u32,
+    /// Player of the last pause chat event and the tick it arrived on
+    pub(crate) player: Option<(u32, i32)>,
+}
+
+impl App {
+    pub(crate) fn process_pause(&mut self, ctx: &Context, grp: &Entity) -> Result<()> {
+        let paused: bool = try_property!(grp, "m_pGameRules.m_bGamePaused").unwrap_or_default();
+        if paused == self.pause.paused {
+            return Ok(());
+        }
+        self.pause.paused = paused;
+
+        let total_paused_ticks: Option<i32> = try_property!(grp, "m_pGameRules.m_nTotalPausedTicks");
+
+        let mut entry = Entry::new(self.time(ctx)?);
+        entry.paused_time = total_paused_ticks.map(|ticks| ticks as f32 / 30.0);
+        if paused {
+            entry.r#type = "pause".to_string().into();
+            entry.team = try_property!(grp, "m_pGameRules.m_iPauseTeam");
+            // The chat event and the rules update land on the same tick or one apart
+            entry.slot = self
+                .pause
+                .player
+                .take()
+                .filter(|&(tick, _)| ctx.tick().saturating_sub(tick) <= 1)
+                .map(|(_, player)| player);
+            self.pause.start_tick = ctx.tick();
+        } else {
+            entry.r#type = "unpause".to_string().into();
+            self.pause.player = None;
+            entry.duration = (ctx.tick().saturating_sub(self.pause.start_tick) as f32 / 30.0).into();
+        }
+        self.output(entry)
+    }
+}
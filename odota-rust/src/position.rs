@@ -0,0 +1,68 @@
+use anyhow::Result;
+
+use d2_stampede::prelude::*;
+
+use crate::map::entity_position;
+use crate::{schedule, App, Entry};
+
+/// Non-hero units such as couriers, summons, creeps and Roshan
+pub(crate) fn is_unit(class: &str) -> bool {
+    (class.starts_with("CDOTA_Unit_") || class.starts_with("CDOTA_BaseNPC")) && !class.starts_with("CDOTA_Unit_Hero_")
+}
+
+impl App {
+    pub(crate) fn process_positions(&mut self, ctx: &Context, pr: &Entity) -> Result<()> {
+        let Some(period) = self.config.position_interval else {
+            return Ok(());
+        };
+
+        let time = self.time(ctx)?;
+        let tick = self.game_tick(ctx)?;
+        if !schedule(&mut self.next_position, tick, period) {
+            return Ok(());
+        }
+
+        for (i, hero) in App::heroes(ctx, pr) {
+            let Some([x, y, z]) = entity_position(hero) else {
+                continue;
+            };
+
+            let mut entry = Entry::new(time);
+            entry.r#type = "position".to_string().into();
+            entry.slot = (i as i32).into();
+            entry.unit = hero.class().name().to_string().into();
+            entry.x = try_property!(hero, "CBodyComponent.m_cellX");
+            entry.y = try_property!(hero, "CBodyComponent.m_cellY");
+            entry.world_x = x.into();
+            entry.world_y = y.into();
+            entry.world_z = z.into();
+            self.output(entry)?;
+        }
+
+        if self.config.unit_positions {
+            let units: Vec<u32> = self.units.iter().copied().collect();
+            for index in units {
+                let Ok(unit) = ctx.entities().get_by_index(index as usize) else {
+                    continue;
+                };
+                let Some([x, y, z]) = entity_position(unit) else {
+                    continue;
+                };
+
+                let mut entry = Entry::new(time);
+                entry.r#type = "position".to_string().into();
+                entry.slot = self.get_player_slot(unit).ok();
+                entry.unit = unit.class().name().to_string().into();
+                entry.ehandle = unit.handle().into();
+                entry.x = try_property!(unit, "CBodyComponent.m_cellX");
+                entry.y = try_property!(unit, "CBodyComponent.m_cellY");
+                entry.world_x = x.into();
+                entry.world_y = y.into();
+                entry.world_z = z.into();
+                self.output(entry)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+//! Gold and experience sources.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use hashbrown::HashMap;
+use serde::Serialize;
+
+use crate::Entry;
+
+/// `EDOTA_ModifyGold_Reason`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum GoldReason {
+    Unspecified,
+    Death,
+    Buyback,
+    PurchaseConsumable,
+    PurchaseItem,
+    AbandonedRedistribute,
+    SellItem,
+    AbilityCost,
+    CheatCommand,
+    SelectionPenalty,
+    GameTick,
+    Building,
+    HeroKill,
+    CreepKill,
+    NeutralKill,
+    RoshanKill,
+    CourierKill,
+    BountyRune,
+    SharedGold,
+    AbilityGold,
+    WardKill,
+    CourierKilledByThisPlayer,
+    Unknown(u32),
+}
+
+impl From<u32> for GoldReason {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => GoldReason::Unspecified,
+            1 => GoldReason::Death,
+            2 => GoldReason::Buyback,
+            3 => GoldReason::PurchaseConsumable,
+            4 => GoldReason::PurchaseItem,
+            5 => GoldReason::AbandonedRedistribute,
+            6 => GoldReason::SellItem,
+            7 => GoldReason::AbilityCost,
+            8 => GoldReason::CheatCommand,
+            9 => GoldReason::SelectionPenalty,
+            10 => GoldReason::GameTick,
+            11 => GoldReason::Building,
+            12 => GoldReason::HeroKill,
+            13 => GoldReason::CreepKill,
+            14 => GoldReason::NeutralKill,
+            15 => GoldReason::RoshanKill,
+            16 => GoldReason::CourierKill,
+            17 => GoldReason::BountyRune,
+            18 => GoldReason::SharedGold,
+            19 => GoldReason::AbilityGold,
+            20 => GoldReason::WardKill,
+            21 => GoldReason::CourierKilledByThisPlayer,
+            x => GoldReason::Unknown(x),
+        }
+    }
+}
+
+impl Display for GoldReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GoldReason::Unspecified => "unspecified",
+            GoldReason::Death => "death",
+            GoldReason::Buyback => "buyback",
+            GoldReason::PurchaseConsumable => "purchase_consumable",
+            GoldReason::PurchaseItem => "purchase_item",
+            GoldReason::AbandonedRedistribute => "abandoned_redistribute",
+            GoldReason::SellItem => "sell_item",
+            GoldReason::AbilityCost => "ability_cost",
+            GoldReason::CheatCommand => "cheat_command",
+            GoldReason::SelectionPenalty => "selection_penalty",
+            GoldReason::GameTick => "passive",
+            GoldReason::Building => "building",
+            GoldReason::HeroKill => "hero_kill",
+            GoldReason::CreepKill => "creep_kill",
+            GoldReason::NeutralKill => "neutral_kill",
+            GoldReason::RoshanKill => "roshan_kill",
+            GoldReason::CourierKill => "courier_kill",
+            GoldReason::BountyRune => "bounty_rune",
+            GoldReason::SharedGold => "shared_gold",
+            GoldReason::AbilityGold => "ability_gold",
+            GoldReason::WardKill => "ward_kill",
+            GoldReason::CourierKilledByThisPlayer => "courier_killed_by_this_player",
+            GoldReason::Unknown(x) => return write!(f, "{}", x),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl GoldReason {
+    /// Reason of a `DotaCombatlogGold` entry
+    pub fn of(entry: &Entry) -> Option<GoldReason> {
+        entry.gold_reason.map(GoldReason::from)
+    }
+
+    /// Whether the combat log reports gold taken from the player under this reason
+    pub fn is_loss(&self) -> bool {
+        matches!(
+            self,
+            GoldReason::Death
+                | GoldReason::Buyback
+                | GoldReason::PurchaseConsumable
+                | GoldReason::PurchaseItem
+                | GoldReason::AbilityCost
+                | GoldReason::SelectionPenalty
+        )
+    }
+}
+
+/// `EDOTA_ModifyXP_Reason`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum XpReason {
+    Unspecified,
+    HeroKill,
+    CreepKill,
+    RoshanKill,
+    TomeOfKnowledge,
+    Outpost,
+    CatchUp,
+    HeroAbility,
+    Unknown(u32),
+}
+
+impl From<u32> for XpReason {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => XpReason::Unspecified,
+            1 => XpReason::HeroKill,
+            2 => XpReason::CreepKill,
+            3 => XpReason::RoshanKill,
+            4 => XpReason::TomeOfKnowledge,
+            5 => XpReason::Outpost,
+            6 => XpReason::CatchUp,
+            7 => XpReason::HeroAbility,
+            x => XpReason::Unknown(x),
+        }
+    }
+}
+
+impl Display for XpReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            XpReason::Unspecified => "unspecified",
+            XpReason::HeroKill => "hero_kill",
+            XpReason::CreepKill => "creep_kill",
+            XpReason::RoshanKill => "roshan_kill",
+            XpReason::TomeOfKnowledge => "tome_of_knowledge",
+            XpReason::Outpost => "outpost",
+            XpReason::CatchUp => "catch_up",
+            XpReason::HeroAbility => "hero_ability",
+            XpReason::Unknown(x) => return write!(f, "{}", x),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl XpReason {
+    /// Reason of a `DotaCombatlogXp` entry
+    pub fn of(entry: &Entry) -> Option<XpReason> {
+        entry.xp_reason.map(XpReason::from)
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct ReasonBreakdown {
+    pub slot: i32,
+    pub gold: HashMap<String, i64>,
+    pub xp: HashMap<String, i64>,
+    pub gold_by_minute: BTreeMap<i32, HashMap<String, i64>>,
+    pub xp_by_minute: BTreeMap<i32, HashMap<String, i64>>,
+}
+
+/// Per player gold and experience totals by reason, with gold losses such as deaths and buybacks
+/// counted as negative
+pub fn reason_breakdown(entries: &[Entry]) -> Vec<ReasonBreakdown> {
+    let mut players: BTreeMap<i32, ReasonBreakdown> = BTreeMap::new();
+    for entry in entries {
+        let (Some(slot), Some(value)) = (entry.slot, entry.value) else {
+            continue;
+        };
+        let value = value as i64;
+        let (reason, value, total, by_minute) = match entry.r#type.as_deref() {
+            Some("DotaCombatlogGold") => {
+                let Some(reason) = GoldReason::of(entry) else {
+                    continue;
+                };
+                let player = players.entry(slot).or_default();
+                let value = if reason.is_loss() { -value } else { value };
+                (reason.to_string(), value, &mut player.gold, &mut player.gold_by_minute)
+            }
+            Some("DotaCombatlogXp") => {
+                let Some(reason) = XpReason::of(entry) else {
+                    continue;
+                };
+                let player = players.entry(slot).or_default();
+                (reason.to_string(), value, &mut player.xp, &mut player.xp_by_minute)
+            }
+            _ => continue,
+        };
+        *by_minute
+            .entry((entry.time / 60.0).floor() as i32)
+            .or_default()
+            .entry(reason.clone())
+            .or_default() += value;
+        *total.entry(reason).or_default() += value;
+    }
+    players
+        .into_iter()
+        .map(|(slot, breakdown)| ReasonBreakdown { slot, ..breakdown })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gold(time: f32, slot: i32, reason: u32, value: u32) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = "DotaCombatlogGold".to_string().into();
+        entry.slot = slot.into();
+        entry.gold_reason = reason.into();
+        entry.value = value.into();
+        entry
+    }
+
+    fn xp(time: f32, slot: i32, reason: u32, value: u32) -> Entry {
+        let mut entry = Entry::new(time);
+        entry.r#type = "DotaCombatlogXp".to_string().into();
+        entry.slot = slot.into();
+        entry.xp_reason = reason.into();
+        entry.value = value.into();
+        entry
+    }
+
+    #[test]
+    fn losses_are_negative() {
+        let entries = [
+            gold(30.0, 0, 13, 40),
+            gold(50.0, 0, 13, 45),
+            gold(70.0, 0, 1, 120),
+            gold(90.0, 0, 2, 600),
+            xp(70.0, 0, 1, 200),
+        ];
+        let breakdown = reason_breakdown(&entries);
+        assert_eq!(breakdown.len(), 1);
+        let player = &breakdown[0];
+        assert_eq!(player.slot, 0);
+        assert_eq!(player.gold["creep_kill"], 85);
+        assert_eq!(player.gold["death"], -120);
+        assert_eq!(player.gold["buyback"], -600);
+        assert_eq!(player.xp["hero_kill"], 200);
+        assert_eq!(player.gold_by_minute[&0]["creep_kill"], 85);
+        assert_eq!(player.gold_by_minute[&1]["death"], -120);
+    }
+}
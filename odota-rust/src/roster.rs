@@ -0,0 +1,127 @@
+//! Everyone in the player resource: players, coaches, casters and spectators.
+
+use std::fmt::{Display, Formatter};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use d2_stampede::prelude::*;
+
+use crate::{App, Entry, STEAM_ID_BASE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Player,
+    Coach,
+    Caster,
+    Spectator,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Role::Player => "player",
+            Role::Coach => "coach",
+            Role::Caster => "caster",
+            Role::Spectator => "spectator",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Role {
+    fn parse(s: &str) -> Option<Role> {
+        match s {
+            "player" => Some(Role::Player),
+            "coach" => Some(Role::Coach),
+            "caster" => Some(Role::Caster),
+            "spectator" => Some(Role::Spectator),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Participant {
+    pub role: Role,
+    /// Row in the player resource
+    pub player_id: i32,
+    /// Only set for players
+    pub player_slot: Option<i32>,
+    pub account_id: u32,
+    pub steam_id: u64,
+    pub name: String,
+    /// Team played or coached
+    pub team: i32,
+    pub hero_id: Option<i32>,
+}
+
+impl Participant {
+    /// Participant described by a `roster` entry
+    pub fn of(entry: &Entry) -> Option<Participant> {
+        if entry.r#type.as_deref() != Some("roster") {
+            return None;
+        }
+        Some(Participant {
+            role: Role::parse(entry.role.as_deref()?)?,
+            player_id: entry.player_id?,
+            player_slot: entry.player_slot,
+            account_id: entry.account_id.unwrap_or_default(),
+            steam_id: entry.steam_id.unwrap_or_default(),
+            name: entry.key.clone().unwrap_or_default(),
+            team: entry.team.unwrap_or_default(),
+            hero_id: entry.hero_id,
+        })
+    }
+}
+
+pub fn roster(entries: &[Entry]) -> Vec<Participant> {
+    entries.iter().filter_map(Participant::of).collect()
+}
+
+impl App {
+    /// One `roster` entry per occupied player resource row
+    pub(crate) fn process_roster(&mut self, ctx: &Context) -> Result<()> {
+        let Ok(pr) = ctx.entities().get_by_class_name("CDOTA_PlayerResource") else {
+            return Ok(());
+        };
+        for i in 0..30 {
+            let Some(steam_id): Option<u64> = try_property!(pr, "m_vecPlayerData.{i:04}.m_iPlayerSteamID") else {
+                break;
+            };
+            let name: String = try_property!(pr, "m_vecPlayerData.{i:04}.m_iszPlayerName").unwrap_or_default();
+            if steam_id == 0 && name.is_empty() {
+                continue;
+            }
+
+            let team: i32 = try_property!(pr, "m_vecPlayerData.{i:04}.m_iPlayerTeam").unwrap_or_default();
+            let coach_team: i32 = try_property!(pr, "m_vecPlayerData.{i:04}.m_eCoachTeam").unwrap_or_default();
+            let broadcaster: bool = try_property!(pr, "m_vecPlayerData.{i:04}.m_bIsBroadcaster").unwrap_or_default();
+            let (role, team) = if coach_team == 2 || coach_team == 3 {
+                (Role::Coach, coach_team)
+            } else if broadcaster {
+                (Role::Caster, team)
+            } else if team == 2 || team == 3 {
+                (Role::Player, team)
+            } else {
+                (Role::Spectator, team)
+            };
+
+            let mut entry = Entry::new(self.time(ctx)?);
+            entry.r#type = "roster".to_string().into();
+            entry.role = role.to_string().into();
+            entry.player_id = i.into();
+            entry.key = name.into();
+            entry.team = team.into();
+            entry.steam_id = steam_id.into();
+            entry.account_id = (steam_id.saturating_sub(STEAM_ID_BASE) as u32).into();
+            if role == Role::Player {
+                entry.player_slot = self.steam_id_to_player_slot.get(&steam_id).copied();
+                entry.hero_id = try_property!(pr, "m_vecPlayerTeamData.{i:04}.m_nSelectedHeroID");
+            }
+            self.output(entry)?;
+        }
+        Ok(())
+    }
+}
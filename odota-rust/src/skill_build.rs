@@ -0,0 +1,175 @@
+//! Skill builds, talents, facets and Aghanim's upgrades.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use hashbrown::HashSet;
+use serde::Serialize;
+
+use d2_stampede::prelude::*;
+
+use crate::{Ability, App, Entry};
+
+const TALENT_TIERS: [u8; 4] = [10, 15, 20, 25];
+
+/// Hero level of the talent tier, `index` counts talents in ability slot order
+pub(crate) fn talent_tier(ability: &str, index: usize) -> Option<u8> {
+    if ability.starts_with("special_bonus_") && ability != "special_bonus_attributes" {
+        TALENT_TIERS.get(index / 2).copied()
+    } else {
+        None
+    }
+}
+
+fn upgrade(modifier: &str) -> Option<&'static str> {
+    if modifier.starts_with("modifier_item_ultimate_scepter") {
+        Some("scepter")
+    } else if modifier.starts_with("modifier_item_aghanims_shard") {
+        Some("shard")
+    } else {
+        None
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SkillTracker {
+    facet_written: [bool; 10],
+    upgrades: HashSet<(i32, &'static str)>,
+    heroes_seen: HashSet<String>,
+    /// Hero combat log name followed by ability name
+    pub(crate) innate: HashSet<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LevelUp {
+    pub time: f32,
+    pub ability: String,
+    pub ability_level: u8,
+    pub hero_level: Option<u8>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SkillBuild {
+    pub slot: i32,
+    pub hero: String,
+    /// Abilities and talents in the order they were leveled
+    pub level_ups: Vec<LevelUp>,
+    /// Abilities leveled with the hero instead of with ability points
+    pub innate: Vec<LevelUp>,
+    /// Chosen talent by tier level
+    pub talents: BTreeMap<u8, String>,
+    pub facet: Option<u32>,
+    pub scepter: Option<f32>,
+    pub shard: Option<f32>,
+}
+
+/// Skill build of every hero, ordered by slot
+pub fn skill_builds(entries: &[Entry]) -> Vec<SkillBuild> {
+    let mut builds: BTreeMap<i32, SkillBuild> = BTreeMap::new();
+    for entry in entries {
+        let Some(slot) = entry.slot else {
+            continue;
+        };
+        let time = entry.precise_time();
+        match entry.r#type.as_deref() {
+            Some("DotaAbilityLevel") => {
+                let ability_level = entry.abilitylevel.unwrap_or_default();
+                if ability_level == 0 {
+                    continue;
+                }
+                let build = builds.entry(slot).or_default();
+                let ability = entry.valuename.clone().unwrap_or_default();
+                if let Some(tier) = entry.value {
+                    build.talents.insert(tier as u8, ability.clone());
+                }
+                let level_up = LevelUp {
+                    time,
+                    ability,
+                    ability_level,
+                    hero_level: entry.level,
+                };
+                if entry.key.as_deref() == Some("innate") {
+                    build.innate.push(level_up);
+                } else {
+                    build.level_ups.push(level_up);
+                }
+            }
+            Some("facet") => builds.entry(slot).or_default().facet = entry.value,
+            Some("scepter") => {
+                builds.entry(slot).or_default().scepter.get_or_insert(time);
+            }
+            Some("shard") => {
+                builds.entry(slot).or_default().shard.get_or_insert(time);
+            }
+            _ => continue,
+        }
+        let build = builds.get_mut(&slot).unwrap();
+        build.slot = slot;
+        if let Some(hero) = &entry.targetname {
+            build.hero.clone_from(hero);
+        }
+    }
+    builds.into_values().collect()
+}
+
+impl App {
+    /// Innate abilities are the ones already leveled when a hero is first seen with all ability points unspent
+    pub(crate) fn detect_innate_abilities(&mut self, hero: &Entity, hero_name: &str, abilities: &[Ability]) {
+        if !self.skills.heroes_seen.insert(hero_name.to_string()) {
+            return;
+        }
+        let level: Option<u8> = try_property!(hero, "m_iCurrentLevel");
+        let points: Option<u8> = try_property!(hero, "m_iAbilityPoints");
+        if level.is_none() || level != points {
+            return;
+        }
+        for ability in abilities.iter().filter(|a| a.level > 0) {
+            self.skills.innate.insert(hero_name.to_string() + &ability.id);
+        }
+    }
+
+    /// `facet` entry once the hero's facet is selected
+    pub(crate) fn process_facet(&mut self, ctx: &Context, pr: &Entity, slot: i32, hero_name: &str) -> Result<()> {
+        if self.skills.facet_written[slot as usize] {
+            return Ok(());
+        }
+        let facet: u32 = try_property!(pr, "m_vecPlayerTeamData.{slot:04}.m_nSelectedHeroVariant").unwrap_or_default();
+        if facet == 0 {
+            return Ok(());
+        }
+        self.skills.facet_written[slot as usize] = true;
+
+        let mut entry = Entry::new(self.time(ctx)?);
+        entry.r#type = "facet".to_string().into();
+        entry.slot = slot.into();
+        entry.targetname = hero_name.to_string().into();
+        entry.value = facet.into();
+        self.output(entry)
+    }
+
+    /// `scepter` and `shard` entries the first time a hero gains the upgrade
+    pub(crate) fn process_upgrade(&mut self, cle: &CombatLogEntry) -> Result<()> {
+        let (Ok(hero), Ok(modifier)) = (cle.target_name(), cle.inflictor_name()) else {
+            return Ok(());
+        };
+        if cle.is_target_illusion().unwrap_or_default() {
+            return Ok(());
+        }
+        let Some(upgrade) = upgrade(modifier) else {
+            return Ok(());
+        };
+        let Some(&slot) = self.name_to_slot.get(hero) else {
+            return Ok(());
+        };
+        if !self.skills.upgrades.insert((slot, upgrade)) {
+            return Ok(());
+        }
+
+        let mut entry = Entry::new(cle.timestamp()?);
+        entry.r#type = upgrade.to_string().into();
+        entry.slot = slot.into();
+        entry.targetname = hero.to_string().into();
+        entry.inflictor = modifier.to_string().into();
+        self.output(entry)
+    }
+}